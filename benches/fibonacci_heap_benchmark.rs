@@ -1,6 +1,6 @@
 // benches/benchmarks.rs
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use fibonacci_heap::FibonacciHeap;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use fibonacci_heap::{FibonacciHeap, MinBinaryHeap, PriorityQueue};
 
 fn bench_insert(c: &mut Criterion) {
     c.bench_function("insert", |b| {
@@ -13,6 +13,12 @@ fn bench_insert(c: &mut Criterion) {
     });
 }
 
+fn bench_from_iter(c: &mut Criterion) {
+    c.bench_function("from_iter", |b| {
+        b.iter(|| black_box(0..1000).collect::<FibonacciHeap<_>>())
+    });
+}
+
 fn bench_extract_min(c: &mut Criterion) {
     c.bench_function("extract_min", |b| {
         b.iter(|| {
@@ -27,13 +33,31 @@ fn bench_extract_min(c: &mut Criterion) {
     });
 }
 
+fn bench_insert_extract_million(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large");
+    group.sample_size(10);
+    group.bench_function("insert_extract_1m", |b| {
+        b.iter(|| {
+            let mut heap = FibonacciHeap::new();
+            for i in 0..1_000_000u32 {
+                // Scatter keys so consolidation sees a realistic mix
+                heap.insert(black_box(i.wrapping_mul(2_654_435_761)))
+                    .unwrap();
+            }
+            while let Some(key) = heap.extract_min() {
+                black_box(key);
+            }
+        })
+    });
+    group.finish();
+}
+
 fn bench_decrease_key(c: &mut Criterion) {
     c.bench_function("decrease_key", |b| {
         b.iter(|| {
             let mut heap = FibonacciHeap::new();
-            let nodes: Vec<_> = (0..1000).map(|i| heap.insert(i).unwrap()).collect();
-            for node in &nodes {
-                let key = node.borrow().key;
+            let nodes: Vec<_> = (0..1000).map(|i| (i, heap.insert(i).unwrap())).collect();
+            for (key, node) in &nodes {
                 heap.decrease_key(node, black_box(key / 2)).unwrap();
             }
         })
@@ -58,11 +82,55 @@ fn bench_merge(c: &mut Criterion) {
     });
 }
 
+fn bench_merge_large(c: &mut Criterion) {
+    c.bench_function("merge_large", |b| {
+        b.iter_batched(
+            || {
+                let mut heap1 = FibonacciHeap::new();
+                let mut heap2 = FibonacciHeap::new();
+                for i in 0..100_000 {
+                    heap1.insert(i).unwrap();
+                    heap2.insert(i).unwrap();
+                }
+                (heap1, heap2)
+            },
+            |(mut heap1, heap2)| {
+                heap1.merge(heap2);
+                heap1
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+/// Pushes scattered keys and pops them all through the shared trait
+fn push_pop<Q: PriorityQueue<Item = u32> + Default>() -> Q {
+    let mut queue = Q::default();
+    for i in 0..1000u32 {
+        queue.push(black_box(i.wrapping_mul(2_654_435_761)));
+    }
+    while let Some(key) = queue.pop() {
+        black_box(key);
+    }
+    queue
+}
+
+fn bench_against_binary_heap(c: &mut Criterion) {
+    let mut group = c.benchmark_group("push_pop");
+    group.bench_function("fibonacci_heap", |b| b.iter(push_pop::<FibonacciHeap<u32>>));
+    group.bench_function("binary_heap", |b| b.iter(push_pop::<MinBinaryHeap<u32>>));
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_insert,
+    bench_from_iter,
     bench_extract_min,
+    bench_insert_extract_million,
     bench_decrease_key,
-    bench_merge
+    bench_merge,
+    bench_merge_large,
+    bench_against_binary_heap
 );
 criterion_main!(benches);
@@ -0,0 +1,296 @@
+//! A* search towards a single target, guided by a heuristic.
+//!
+//! Open nodes sit in a [`KeyedFibonacciHeap`] keyed by their estimated total
+//! cost, the distance so far plus the heuristic. A cheaper path to an open
+//! node lowers its entry with `decrease_key`; a cheaper path to a closed node
+//! reopens it, so heuristics that are admissible but not consistent still
+//! yield shortest paths.
+
+use std::collections::hash_map::{Entry, HashMap};
+
+use super::{Graph, Weight};
+use crate::{KeyedFibonacciHeap, NodeHandle};
+
+/// The outcome of an A* search
+#[derive(Debug, Clone)]
+pub struct SearchResult<N, W> {
+    route: Option<(W, Vec<N>)>,
+    explored: usize,
+}
+
+impl<N, W: Copy> SearchResult<N, W> {
+    /// Returns the nodes along the path found, from source to target
+    pub fn path(&self) -> Option<&[N]> {
+        self.route.as_ref().map(|(_, path)| path.as_slice())
+    }
+
+    /// Returns the length of the path found
+    pub fn cost(&self) -> Option<W> {
+        self.route.as_ref().map(|&(cost, _)| cost)
+    }
+
+    /// Returns how many nodes were expanded, counting a reopened node each time
+    pub fn explored(&self) -> usize {
+        self.explored
+    }
+
+    /// Consumes the result, returning the path length and its nodes
+    pub fn into_route(self) -> Option<(W, Vec<N>)> {
+        self.route
+    }
+}
+
+/// Finds a shortest path from `source` to `target`, expanding nodes in order of `heuristic`
+///
+/// `heuristic(node)` estimates the remaining distance from `node` to
+/// `target` and is called once per discovered node. The path is shortest
+/// whenever the heuristic never overestimates; it need not be consistent,
+/// since closed nodes are reopened when a cheaper path to them turns up.
+///
+/// Costs must implement [`Weight`], which covers the integer types; see its
+/// documentation for using `f64` distances.
+///
+/// # Examples
+/// ```
+/// use fibonacci_heap::algorithms::astar::astar;
+///
+/// // Nodes on a line, estimated by how far they are from the target
+/// let graph = vec![
+///     vec![(1, 1)],
+///     vec![(0, 1), (2, 1)],
+///     vec![(1, 1), (3, 1)],
+///     vec![(2, 1)],
+/// ];
+///
+/// let result = astar(&graph, 0, 3, |node: usize| 3 - node as u32);
+/// assert_eq!(result.cost(), Some(3));
+/// assert_eq!(result.path(), Some(&[0, 1, 2, 3][..]));
+/// assert_eq!(result.explored(), 4);
+/// ```
+pub fn astar<G, H>(
+    graph: &G,
+    source: G::Node,
+    target: G::Node,
+    mut heuristic: H,
+) -> SearchResult<G::Node, G::Weight>
+where
+    G: Graph,
+    H: FnMut(G::Node) -> G::Weight,
+{
+    let mut queue = KeyedFibonacciHeap::new();
+    let mut states = HashMap::new();
+    let mut predecessors = HashMap::new();
+    let mut explored = 0;
+
+    let estimate = heuristic(source);
+    let handle = queue
+        .insert(G::Weight::ZERO + estimate, source)
+        .expect("inserting into a heap cannot fail");
+    states.insert(source, State::Open(handle, G::Weight::ZERO, estimate));
+
+    while let Some((_, node)) = queue.extract_min() {
+        let (distance, estimate) = match states[&node] {
+            State::Open(_, distance, estimate) => (distance, estimate),
+            State::Closed(..) => unreachable!("closed nodes are not queued"),
+        };
+        states.insert(node, State::Closed(distance, estimate));
+        explored += 1;
+
+        if node == target {
+            let mut path = vec![node];
+            while let Some(&previous) = predecessors.get(&path[path.len() - 1]) {
+                path.push(previous);
+            }
+            path.reverse();
+            return SearchResult {
+                route: Some((distance, path)),
+                explored,
+            };
+        }
+
+        for (next, weight) in graph.neighbors(node) {
+            let candidate = distance + weight;
+            match states.entry(next) {
+                Entry::Vacant(entry) => {
+                    let estimate = heuristic(next);
+                    let handle = queue
+                        .insert(candidate + estimate, next)
+                        .expect("inserting into a heap cannot fail");
+                    entry.insert(State::Open(handle, candidate, estimate));
+                }
+                Entry::Occupied(mut entry) => match *entry.get() {
+                    State::Open(handle, current, estimate) if candidate < current => {
+                        queue
+                            .decrease_key(&handle, candidate + estimate)
+                            .expect("open nodes have live handles");
+                        entry.insert(State::Open(handle, candidate, estimate));
+                    }
+                    State::Closed(current, estimate) if candidate < current => {
+                        let handle = queue
+                            .insert(candidate + estimate, next)
+                            .expect("inserting into a heap cannot fail");
+                        entry.insert(State::Open(handle, candidate, estimate));
+                    }
+                    _ => continue,
+                },
+            }
+            predecessors.insert(next, node);
+        }
+    }
+
+    SearchResult {
+        route: None,
+        explored,
+    }
+}
+
+/// Progress of a node through the search, with its distance and heuristic estimate
+enum State<W> {
+    /// In the queue, keyed by distance plus estimate
+    Open(NodeHandle, W, W),
+    /// Expanded; reopened if a shorter distance is found
+    Closed(W, W),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::dijkstra::{dijkstra, shortest_path};
+    use crate::algorithms::testing::{path_length, random_graph};
+    use crate::testing::Lcg;
+    use std::vec;
+
+    /// A four-connected grid where `#` cells are walls and every step costs 1
+    struct Grid {
+        cells: Vec<Vec<u8>>,
+    }
+
+    impl Grid {
+        fn parse(rows: &[&str]) -> Self {
+            Grid {
+                cells: rows.iter().map(|row| row.bytes().collect()).collect(),
+            }
+        }
+
+        fn manhattan(to: (usize, usize)) -> impl Fn((usize, usize)) -> u32 {
+            move |at: (usize, usize)| (at.0.abs_diff(to.0) + at.1.abs_diff(to.1)) as u32
+        }
+    }
+
+    impl Graph for Grid {
+        type Node = (usize, usize);
+        type Weight = u32;
+        type Neighbors<'a> = vec::IntoIter<((usize, usize), u32)>;
+
+        fn neighbors(&self, (row, column): (usize, usize)) -> Self::Neighbors<'_> {
+            let steps = [
+                (row.wrapping_sub(1), column),
+                (row + 1, column),
+                (row, column.wrapping_sub(1)),
+                (row, column + 1),
+            ];
+            steps
+                .into_iter()
+                .filter(|&(row, column)| {
+                    self.cells
+                        .get(row)
+                        .and_then(|cells| cells.get(column))
+                        .is_some_and(|&cell| cell != b'#')
+                })
+                .map(|at| (at, 1))
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+    }
+
+    #[test]
+    fn test_grid_with_walls() {
+        let grid = Grid::parse(&[
+            "..........",
+            ".########.",
+            ".#......#.",
+            ".#.####.#.",
+            "...#..#...",
+            "####..####",
+        ]);
+        let (source, target) = ((2, 2), (0, 5));
+
+        let guided = astar(&grid, source, target, Grid::manhattan(target));
+        let blind = astar(&grid, source, target, |_| 0);
+        let (cost, _) = shortest_path(&grid, source, target).unwrap();
+        assert_eq!(guided.cost(), Some(cost));
+        assert_eq!(blind.cost(), Some(cost));
+        assert!(guided.explored() < blind.explored());
+
+        // The path is made of single steps between open cells
+        let path = guided.path().unwrap();
+        assert_eq!((path[0], path[path.len() - 1]), (source, target));
+        assert_eq!(path.len() as u32, cost + 1);
+        for step in path.windows(2) {
+            assert!(grid.neighbors(step[0]).any(|(next, _)| next == step[1]));
+        }
+
+        // Walled off: every reachable cell gets expanded before giving up
+        let result = astar(&grid, (0, 0), (5, 4), Grid::manhattan((5, 4)));
+        assert_eq!(result.path(), None);
+        assert_eq!(result.cost(), None);
+        assert_eq!(result.explored(), 30);
+    }
+
+    #[test]
+    fn test_inconsistent_heuristic_reopens_nodes() {
+        // S=0, A=1, B=2, C=3, G=4: the estimate for A delays it until C is
+        // closed through B, so C must be reopened once A is expanded
+        let graph = vec![
+            vec![(1, 1), (2, 3)],
+            vec![(3, 1)],
+            vec![(3, 0)],
+            vec![(4, 10)],
+            vec![],
+        ];
+        let estimates = [0, 5, 0, 0, 0];
+
+        let result = astar(&graph, 0, 4, |node: usize| estimates[node]);
+        assert_eq!(result.cost(), Some(12));
+        assert_eq!(result.path(), Some(&[0, 1, 3, 4][..]));
+        assert_eq!(result.explored(), 6);
+        assert_eq!(result.into_route(), Some((12, vec![0, 1, 3, 4])));
+
+        let result = astar(&graph, 4, 4, |_| 7);
+        assert_eq!(result.into_route(), Some((0, vec![4])));
+    }
+
+    #[test]
+    fn test_matches_dijkstra_on_random_graphs() {
+        let mut rng = Lcg::new(0x6a09_e667_f3bc_c908);
+
+        for _ in 0..20 {
+            let graph = random_graph(&mut rng);
+            let target = rng.next(graph.len() as u64) as usize;
+            let expected = shortest_path(&graph, 0, target);
+
+            // Half the true distance on odd nodes only: admissible, rarely consistent
+            let exact = dijkstra(&reversed(&graph), target);
+            let heuristic = |node: usize| match exact.distance(node) {
+                Some(distance) if node % 2 == 1 => distance / 2,
+                _ => 0,
+            };
+            let result = astar(&graph, 0, target, heuristic);
+            assert_eq!(result.cost(), expected.as_ref().map(|route| route.0));
+            if let Some(path) = result.path() {
+                assert_eq!(result.cost(), Some(path_length(&graph, path)));
+            }
+        }
+    }
+
+    /// Reverses every edge, so distances from the target become distances to it
+    fn reversed(graph: &[Vec<(usize, u64)>]) -> Vec<Vec<(usize, u64)>> {
+        let mut reversed = vec![Vec::new(); graph.len()];
+        for (from, edges) in graph.iter().enumerate() {
+            for &(to, weight) in edges {
+                reversed[to].push((from, weight));
+            }
+        }
+        reversed
+    }
+}
@@ -0,0 +1,297 @@
+//! Dijkstra's single- and multi-source shortest paths.
+//!
+//! Every discovered node keeps one entry in a [`KeyedFibonacciHeap`]; finding
+//! a shorter path lowers that entry with `decrease_key` through its handle
+//! instead of queueing a duplicate.
+
+use std::collections::hash_map::{Entry, HashMap};
+
+use super::{Graph, Weight};
+use crate::{KeyedFibonacciHeap, NodeHandle};
+
+/// Shortest distances and predecessors found by a Dijkstra search
+///
+/// Only nodes whose distance is final are included; with an early exit that
+/// is the target and every node settled before it.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N, W> {
+    distances: HashMap<N, W>,
+    predecessors: HashMap<N, N>,
+}
+
+impl<N: Copy + Eq + std::hash::Hash, W: Copy> ShortestPaths<N, W> {
+    /// Returns the length of the shortest path to `node`, if it was reached
+    pub fn distance(&self, node: N) -> Option<W> {
+        self.distances.get(&node).copied()
+    }
+
+    /// Returns the node before `node` on its shortest path
+    ///
+    /// Sources, and nodes that were not reached, have no predecessor.
+    pub fn predecessor(&self, node: N) -> Option<N> {
+        self.distances.get(&node)?;
+        self.predecessors.get(&node).copied()
+    }
+
+    /// Reconstructs the shortest path to `node`, starting at its nearest source
+    pub fn path_to(&self, node: N) -> Option<Vec<N>> {
+        self.distances.get(&node)?;
+        let mut path = vec![node];
+        while let Some(&previous) = self.predecessors.get(path.last()?) {
+            path.push(previous);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Returns the final distance of every reached node
+    pub fn distances(&self) -> &HashMap<N, W> {
+        &self.distances
+    }
+}
+
+/// Computes shortest paths from `source` to every reachable node
+///
+/// # Examples
+/// ```
+/// use fibonacci_heap::algorithms::dijkstra::dijkstra;
+///
+/// let graph = vec![
+///     vec![(1, 4), (2, 1)],
+///     vec![(3, 1)],
+///     vec![(1, 2), (3, 5)],
+///     vec![],
+/// ];
+///
+/// let paths = dijkstra(&graph, 0);
+/// assert_eq!(paths.distance(3), Some(4));
+/// assert_eq!(paths.path_to(3), Some(vec![0, 2, 1, 3]));
+/// ```
+pub fn dijkstra<G: Graph>(graph: &G, source: G::Node) -> ShortestPaths<G::Node, G::Weight> {
+    search(graph, [source], None)
+}
+
+/// Computes shortest paths from the nearest of several sources
+///
+/// Every source starts at distance zero, and each path returned by
+/// [`ShortestPaths::path_to`] begins at the source closest to its end.
+///
+/// # Examples
+/// ```
+/// use fibonacci_heap::algorithms::dijkstra::dijkstra_multi;
+///
+/// // Two depots at the ends of a road with four stops
+/// let graph = vec![
+///     vec![(1, 3)],
+///     vec![(0, 3), (2, 3)],
+///     vec![(1, 3), (3, 1)],
+///     vec![(2, 1)],
+/// ];
+///
+/// let paths = dijkstra_multi(&graph, [0, 3]);
+/// assert_eq!(paths.distance(1), Some(3));
+/// assert_eq!(paths.path_to(2), Some(vec![3, 2]));
+/// ```
+pub fn dijkstra_multi<G, I>(graph: &G, sources: I) -> ShortestPaths<G::Node, G::Weight>
+where
+    G: Graph,
+    I: IntoIterator<Item = G::Node>,
+{
+    search(graph, sources, None)
+}
+
+/// Finds a shortest path from `source` to `target`, stopping as soon as it is known
+///
+/// Returns the path length and the nodes along it, or `None` if `target`
+/// cannot be reached.
+///
+/// # Examples
+/// ```
+/// use fibonacci_heap::algorithms::dijkstra::shortest_path;
+///
+/// let graph = vec![vec![(1, 7), (2, 2)], vec![], vec![(1, 3)]];
+/// assert_eq!(shortest_path(&graph, 0, 1), Some((5, vec![0, 2, 1])));
+/// assert_eq!(shortest_path(&graph, 1, 0), None);
+/// ```
+pub fn shortest_path<G: Graph>(
+    graph: &G,
+    source: G::Node,
+    target: G::Node,
+) -> Option<(G::Weight, Vec<G::Node>)> {
+    let paths = search(graph, [source], Some(target));
+    Some((paths.distance(target)?, paths.path_to(target)?))
+}
+
+/// Progress of a node through the search
+enum State<W> {
+    /// In the queue with a tentative distance
+    Queued(NodeHandle, W),
+    /// Removed from the queue with its final distance
+    Settled,
+}
+
+/// Runs Dijkstra's algorithm from `sources`, stopping early once `target` is settled
+fn search<G, I>(graph: &G, sources: I, target: Option<G::Node>) -> ShortestPaths<G::Node, G::Weight>
+where
+    G: Graph,
+    I: IntoIterator<Item = G::Node>,
+{
+    let mut queue = KeyedFibonacciHeap::new();
+    let mut states = HashMap::new();
+    let mut paths = ShortestPaths {
+        distances: HashMap::new(),
+        predecessors: HashMap::new(),
+    };
+
+    for source in sources {
+        if let Entry::Vacant(entry) = states.entry(source) {
+            let handle = queue
+                .insert(G::Weight::ZERO, source)
+                .expect("inserting into a heap cannot fail");
+            entry.insert(State::Queued(handle, G::Weight::ZERO));
+        }
+    }
+
+    while let Some((distance, node)) = queue.extract_min() {
+        states.insert(node, State::Settled);
+        paths.distances.insert(node, distance);
+        if Some(node) == target {
+            break;
+        }
+
+        for (next, weight) in graph.neighbors(node) {
+            let candidate = distance + weight;
+            match states.entry(next) {
+                Entry::Vacant(entry) => {
+                    let handle = queue
+                        .insert(candidate, next)
+                        .expect("inserting into a heap cannot fail");
+                    entry.insert(State::Queued(handle, candidate));
+                }
+                Entry::Occupied(mut entry) => match *entry.get() {
+                    State::Queued(handle, current) if candidate < current => {
+                        queue
+                            .decrease_key(&handle, candidate)
+                            .expect("queued nodes have live handles");
+                        entry.insert(State::Queued(handle, candidate));
+                    }
+                    _ => continue,
+                },
+            }
+            paths.predecessors.insert(next, node);
+        }
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::testing::{path_length, random_graph};
+    use crate::testing::Lcg;
+    use std::cell::Cell;
+
+    /// The directed example graph from CLRS, with nodes s, t, x, y, z as 0..5
+    fn clrs() -> Vec<Vec<(usize, u32)>> {
+        vec![
+            vec![(1, 10), (3, 5)],
+            vec![(2, 1), (3, 2)],
+            vec![(4, 4)],
+            vec![(1, 3), (2, 9), (4, 2)],
+            vec![(0, 7), (2, 6)],
+        ]
+    }
+
+    #[test]
+    fn test_single_source() {
+        let paths = dijkstra(&clrs(), 0);
+        let distances: Vec<_> = (0..5).map(|node| paths.distance(node)).collect();
+        assert_eq!(distances, vec![Some(0), Some(8), Some(9), Some(5), Some(7)]);
+        assert_eq!(paths.path_to(2), Some(vec![0, 3, 1, 2]));
+        assert_eq!(paths.predecessor(0), None);
+        assert_eq!(paths.predecessor(4), Some(3));
+
+        let mut graph = clrs();
+        graph.push(vec![(0, 1)]);
+        let paths = dijkstra(&graph, 0);
+        assert_eq!(paths.distance(5), None);
+        assert_eq!(paths.path_to(5), None);
+        assert_eq!(paths.distances().len(), 5);
+    }
+
+    #[test]
+    fn test_multi_source() {
+        let paths = dijkstra_multi(&clrs(), [1, 4, 1]);
+        assert_eq!(paths.distance(1), Some(0));
+        assert_eq!(paths.distance(4), Some(0));
+        assert_eq!(paths.distance(2), Some(1));
+        assert_eq!(paths.distance(0), Some(7));
+        assert_eq!(paths.path_to(3), Some(vec![1, 3]));
+        assert_eq!(paths.path_to(0), Some(vec![4, 0]));
+    }
+
+    /// A graph that counts how many nodes had their edges expanded
+    struct Counting {
+        graph: Vec<Vec<(usize, u64)>>,
+        expanded: Cell<usize>,
+    }
+
+    impl Graph for Counting {
+        type Node = usize;
+        type Weight = u64;
+        type Neighbors<'a> = <Vec<Vec<(usize, u64)>> as Graph>::Neighbors<'a>;
+
+        fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+            self.expanded.set(self.expanded.get() + 1);
+            self.graph.neighbors(node)
+        }
+    }
+
+    #[test]
+    fn test_early_exit_stops_at_target() {
+        // A long chain where the target sits close to the source
+        let graph = Counting {
+            graph: (0..1000).map(|node| vec![((node + 1) % 1000, 1)]).collect(),
+            expanded: Cell::new(0),
+        };
+        assert_eq!(shortest_path(&graph, 0, 3), Some((3, vec![0, 1, 2, 3])));
+        assert_eq!(graph.expanded.get(), 3);
+        assert_eq!(shortest_path(&graph, 5, 5), Some((0, vec![5])));
+    }
+
+    #[test]
+    fn test_matches_bellman_ford_on_random_graphs() {
+        let mut rng = Lcg::new(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0..20 {
+            let graph = random_graph(&mut rng);
+            let nodes = graph.len();
+
+            // Reference distances by repeated relaxation of every edge
+            let mut expected = vec![None; nodes];
+            expected[0] = Some(0);
+            for _ in 0..nodes {
+                for (from, edges) in graph.iter().enumerate() {
+                    for &(to, weight) in edges {
+                        if let Some(base) = expected[from] {
+                            match expected[to] {
+                                Some(current) if current <= base + weight => {}
+                                _ => expected[to] = Some(base + weight),
+                            }
+                        }
+                    }
+                }
+            }
+
+            let paths = dijkstra(&graph, 0);
+            for (node, &distance) in expected.iter().enumerate() {
+                assert_eq!(paths.distance(node), distance);
+                // Paths must be real edges adding up to the distance
+                if let Some(path) = paths.path_to(node) {
+                    assert_eq!(Some(path_length(&graph, &path)), distance);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! Graph algorithms built on the heap's decrease-key support.
+//!
+//! The algorithms work on any type implementing [`Graph`]. Adjacency lists
+//! of the form `Vec<Vec<(usize, W)>>`, where `graph[u]` lists the edges
+//! leaving node `u`, implement it out of the box. Algorithms on undirected
+//! graphs additionally need [`UndirectedGraph`] to enumerate every node.
+
+use std::hash::Hash;
+use std::iter::Copied;
+use std::ops::{Add, Range};
+use std::slice;
+
+pub mod astar;
+pub mod dijkstra;
+pub mod prim;
+
+/// A directed graph with weighted edges
+///
+/// # Examples
+/// ```
+/// use fibonacci_heap::algorithms::Graph;
+///
+/// /// Nodes on a line, each connected to the next one at cost 1
+/// struct Line(u32);
+///
+/// impl Graph for Line {
+///     type Node = u32;
+///     type Weight = u32;
+///     type Neighbors<'a> = std::option::IntoIter<(u32, u32)>;
+///
+///     fn neighbors(&self, node: u32) -> Self::Neighbors<'_> {
+///         (node + 1 < self.0).then_some((node + 1, 1)).into_iter()
+///     }
+/// }
+///
+/// assert_eq!(Line(3).neighbors(1).collect::<Vec<_>>(), vec![(2, 1)]);
+/// ```
+pub trait Graph {
+    /// Identifies a node
+    type Node: Copy + Eq + Hash;
+    /// The cost of traversing an edge
+    type Weight: Weight;
+    /// Iterator over the edges leaving a node
+    type Neighbors<'a>: Iterator<Item = (Self::Node, Self::Weight)>
+    where
+        Self: 'a;
+
+    /// Returns the edges leaving `node` as `(target, weight)` pairs
+    fn neighbors(&self, node: Self::Node) -> Self::Neighbors<'_>;
+}
+
+/// A graph whose edges can be followed in both directions, with a known set of nodes
+///
+/// Every edge must be reported by [`Graph::neighbors`] from both of its
+/// ends, with the same weight.
+pub trait UndirectedGraph: Graph {
+    /// Iterator over all nodes of the graph
+    type Nodes<'a>: Iterator<Item = Self::Node>
+    where
+        Self: 'a;
+
+    /// Returns every node of the graph, including isolated ones
+    fn nodes(&self) -> Self::Nodes<'_>;
+}
+
+/// An edge weight: totally ordered, summable and with a zero
+///
+/// Implemented for the primitive integer types. The algorithms assume
+/// weights are never negative.
+///
+/// Floating-point numbers are not [`Ord`], so `f64` distances need a wrapper
+/// that picks a total order, for example with [`f64::total_cmp`]. NaN must
+/// not appear as a weight or estimate.
+///
+/// # Examples
+/// ```
+/// use std::cmp::Ordering;
+/// use std::ops::Add;
+///
+/// use fibonacci_heap::algorithms::astar::astar;
+/// use fibonacci_heap::algorithms::Weight;
+///
+/// #[derive(Debug, Clone, Copy)]
+/// struct Distance(f64);
+///
+/// impl PartialEq for Distance {
+///     fn eq(&self, other: &Self) -> bool {
+///         self.cmp(other) == Ordering::Equal
+///     }
+/// }
+///
+/// impl Eq for Distance {}
+///
+/// impl PartialOrd for Distance {
+///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+///         Some(self.cmp(other))
+///     }
+/// }
+///
+/// impl Ord for Distance {
+///     fn cmp(&self, other: &Self) -> Ordering {
+///         self.0.total_cmp(&other.0)
+///     }
+/// }
+///
+/// impl Add for Distance {
+///     type Output = Self;
+///
+///     fn add(self, other: Self) -> Self {
+///         Distance(self.0 + other.0)
+///     }
+/// }
+///
+/// impl Weight for Distance {
+///     const ZERO: Self = Distance(0.0);
+/// }
+///
+/// // Points in the plane, estimated by the straight-line distance to the last one
+/// let points: [(f64, f64); 4] = [(0.0, 0.0), (3.0, 4.0), (6.0, 0.0), (6.0, 8.0)];
+/// let between = |a: usize, b: usize| {
+///     let (dx, dy) = (points[a].0 - points[b].0, points[a].1 - points[b].1);
+///     Distance((dx * dx + dy * dy).sqrt())
+/// };
+/// let graph = vec![
+///     vec![(1, between(0, 1)), (2, between(0, 2))],
+///     vec![(3, between(1, 3))],
+///     vec![(3, between(2, 3))],
+///     vec![],
+/// ];
+///
+/// let result = astar(&graph, 0, 3, |node: usize| between(node, 3));
+/// assert_eq!(result.cost(), Some(Distance(10.0)));
+/// assert_eq!(result.path(), Some(&[0, 1, 3][..]));
+/// ```
+pub trait Weight: Copy + Ord + Add<Output = Self> {
+    /// The weight of an empty path
+    const ZERO: Self;
+}
+
+macro_rules! impl_weight {
+    ($($int:ty),*) => {
+        $(impl Weight for $int {
+            const ZERO: Self = 0;
+        })*
+    };
+}
+
+impl_weight!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl<W: Weight> Graph for Vec<Vec<(usize, W)>> {
+    type Node = usize;
+    type Weight = W;
+    type Neighbors<'a>
+        = Copied<slice::Iter<'a, (usize, W)>>
+    where
+        W: 'a;
+
+    fn neighbors(&self, node: usize) -> Self::Neighbors<'_> {
+        self[node].iter().copied()
+    }
+}
+
+/// Treats the adjacency list as undirected; each edge must be listed under both ends
+impl<W: Weight> UndirectedGraph for Vec<Vec<(usize, W)>> {
+    type Nodes<'a>
+        = Range<usize>
+    where
+        W: 'a;
+
+    fn nodes(&self) -> Range<usize> {
+        0..self.len()
+    }
+}
+
+/// Random graphs shared by the algorithm tests
+#[cfg(test)]
+mod testing {
+    use crate::testing::Lcg;
+
+    /// Builds a directed graph of 2 to 41 nodes with up to 5 edges each,
+    /// including self loops and parallel edges
+    pub fn random_graph(rng: &mut Lcg) -> Vec<Vec<(usize, u64)>> {
+        let nodes = 2 + rng.next(40) as usize;
+        (0..nodes)
+            .map(|_| {
+                (0..rng.next(6))
+                    .map(|_| (rng.next(nodes as u64) as usize, rng.next(20)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Sums the cheapest edge between each pair of consecutive nodes,
+    /// panicking if the path uses an edge the graph does not have
+    pub fn path_length(graph: &[Vec<(usize, u64)>], path: &[usize]) -> u64 {
+        path.windows(2)
+            .map(|hop| {
+                graph[hop[0]]
+                    .iter()
+                    .filter(|edge| edge.0 == hop[1])
+                    .map(|edge| edge.1)
+                    .min()
+                    .expect("path follows an edge of the graph")
+            })
+            .sum()
+    }
+}
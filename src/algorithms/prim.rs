@@ -0,0 +1,245 @@
+//! Prim's minimum spanning tree, extended to forests.
+//!
+//! Nodes outside the tree wait in a [`KeyedFibonacciHeap`] keyed by their
+//! cheapest known connecting edge, which `decrease_key` lowers as the tree
+//! grows. Once a tree cannot grow further, the search restarts from the next
+//! node not yet covered, so disconnected graphs yield one tree per component.
+
+use std::collections::hash_map::{Entry, HashMap};
+
+use super::{UndirectedGraph, Weight};
+use crate::{KeyedFibonacciHeap, NodeHandle};
+
+/// A minimum spanning forest: one minimum spanning tree per connected component
+#[derive(Debug, Clone)]
+pub struct SpanningForest<N, W> {
+    edges: Vec<(N, N, W)>,
+    total_weight: W,
+    trees: usize,
+}
+
+impl<N, W: Copy> SpanningForest<N, W> {
+    /// Returns the chosen edges as `(parent, child, weight)`, in the order they were added
+    pub fn edges(&self) -> &[(N, N, W)] {
+        &self.edges
+    }
+
+    /// Returns the sum of the weights of all chosen edges
+    pub fn total_weight(&self) -> W {
+        self.total_weight
+    }
+
+    /// Returns the number of trees, which equals the number of connected components
+    pub fn trees(&self) -> usize {
+        self.trees
+    }
+}
+
+/// Computes a minimum spanning forest of an undirected graph
+///
+/// # Examples
+/// ```
+/// use fibonacci_heap::algorithms::prim::minimum_spanning_forest;
+///
+/// // A triangle 0-1-2 and a separate edge 3-4
+/// let graph = vec![
+///     vec![(1, 1), (2, 3)],
+///     vec![(0, 1), (2, 1)],
+///     vec![(0, 3), (1, 1)],
+///     vec![(4, 5)],
+///     vec![(3, 5)],
+/// ];
+///
+/// let forest = minimum_spanning_forest(&graph);
+/// assert_eq!(forest.total_weight(), 7);
+/// assert_eq!(forest.trees(), 2);
+/// assert_eq!(forest.edges(), &[(0, 1, 1), (1, 2, 1), (3, 4, 5)]);
+/// ```
+pub fn minimum_spanning_forest<G: UndirectedGraph>(
+    graph: &G,
+) -> SpanningForest<G::Node, G::Weight> {
+    let mut forest = SpanningForest {
+        edges: Vec::new(),
+        total_weight: G::Weight::ZERO,
+        trees: 0,
+    };
+    let mut queue = KeyedFibonacciHeap::new();
+    let mut states = HashMap::new();
+
+    for root in graph.nodes() {
+        if states.contains_key(&root) {
+            continue;
+        }
+        forest.trees += 1;
+        grow(graph, root, &mut queue, &mut states, &mut forest);
+    }
+
+    forest
+}
+
+/// Progress of a node through the search
+enum State<N, W> {
+    /// Waiting to join, with its cheapest known edge into the tree
+    Queued(NodeHandle, W, N),
+    /// Part of the forest
+    InTree,
+}
+
+/// Grows the tree containing `root` until no edge leads out of it
+fn grow<G: UndirectedGraph>(
+    graph: &G,
+    root: G::Node,
+    queue: &mut KeyedFibonacciHeap<G::Weight, G::Node>,
+    states: &mut HashMap<G::Node, State<G::Node, G::Weight>>,
+    forest: &mut SpanningForest<G::Node, G::Weight>,
+) {
+    let mut next = Some(root);
+    while let Some(node) = next {
+        if let Some(State::Queued(_, weight, parent)) = states.insert(node, State::InTree) {
+            forest.edges.push((parent, node, weight));
+            forest.total_weight = forest.total_weight + weight;
+        }
+
+        for (neighbor, weight) in graph.neighbors(node) {
+            match states.entry(neighbor) {
+                Entry::Vacant(entry) => {
+                    let handle = queue
+                        .insert(weight, neighbor)
+                        .expect("inserting into a heap cannot fail");
+                    entry.insert(State::Queued(handle, weight, node));
+                }
+                Entry::Occupied(mut entry) => {
+                    if let State::Queued(handle, current, _) = *entry.get() {
+                        if weight < current {
+                            queue
+                                .decrease_key(&handle, weight)
+                                .expect("queued nodes have live handles");
+                            entry.insert(State::Queued(handle, weight, node));
+                        }
+                    }
+                }
+            }
+        }
+
+        next = queue.extract_min().map(|(_, node)| node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lcg;
+
+    /// Builds a symmetric adjacency list from an edge list
+    fn undirected(nodes: usize, edges: &[(usize, usize, u32)]) -> Vec<Vec<(usize, u32)>> {
+        let mut graph = vec![Vec::new(); nodes];
+        for &(a, b, weight) in edges {
+            graph[a].push((b, weight));
+            graph[b].push((a, weight));
+        }
+        graph
+    }
+
+    #[test]
+    fn test_connected_graph() {
+        // The undirected example graph from CLRS, with nodes a..i as 0..9
+        let graph = undirected(
+            9,
+            &[
+                (0, 1, 4),
+                (0, 7, 8),
+                (1, 2, 8),
+                (1, 7, 11),
+                (2, 3, 7),
+                (2, 8, 2),
+                (2, 5, 4),
+                (3, 4, 9),
+                (3, 5, 14),
+                (4, 5, 10),
+                (5, 6, 2),
+                (6, 7, 1),
+                (6, 8, 6),
+                (7, 8, 7),
+            ],
+        );
+
+        let forest = minimum_spanning_forest(&graph);
+        assert_eq!(forest.total_weight(), 37);
+        assert_eq!(forest.trees(), 1);
+        assert_eq!(forest.edges().len(), 8);
+        for &(parent, child, weight) in forest.edges() {
+            assert!(graph[parent].contains(&(child, weight)));
+        }
+    }
+
+    #[test]
+    fn test_disconnected_graph() {
+        // Two components, an isolated node, a self loop and parallel edges
+        let graph = undirected(
+            7,
+            &[
+                (0, 1, 5),
+                (0, 1, 2),
+                (1, 2, 3),
+                (2, 0, 1),
+                (4, 5, 6),
+                (5, 6, 1),
+                (4, 6, 2),
+                (5, 5, 0),
+            ],
+        );
+
+        let forest = minimum_spanning_forest(&graph);
+        assert_eq!(forest.trees(), 3);
+        assert_eq!(forest.total_weight(), 3 + 3);
+        assert_eq!(forest.edges().len(), 7 - 3);
+        assert!(forest.edges().iter().all(|edge| edge.0 != 3 && edge.1 != 3));
+
+        let empty: Vec<Vec<(usize, u32)>> = Vec::new();
+        assert_eq!(minimum_spanning_forest(&empty).trees(), 0);
+    }
+
+    #[test]
+    fn test_matches_kruskal_on_random_graphs() {
+        let mut rng = Lcg::new(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..20 {
+            let nodes = 1 + rng.next(30) as usize;
+            let edges: Vec<_> = (0..rng.next(60))
+                .map(|_| {
+                    (
+                        rng.next(nodes as u64) as usize,
+                        rng.next(nodes as u64) as usize,
+                        rng.next(50) as u32,
+                    )
+                })
+                .collect();
+            let forest = minimum_spanning_forest(&undirected(nodes, &edges));
+
+            // Reference: Kruskal with a union-find over sorted edges
+            let mut parent: Vec<_> = (0..nodes).collect();
+            fn find(parent: &mut [usize], node: usize) -> usize {
+                if parent[node] != node {
+                    parent[node] = find(parent, parent[node]);
+                }
+                parent[node]
+            }
+            let mut sorted = edges.clone();
+            sorted.sort_by_key(|edge| edge.2);
+            let mut total = 0;
+            let mut joined = 0;
+            for (a, b, weight) in sorted {
+                let (a, b) = (find(&mut parent, a), find(&mut parent, b));
+                if a != b {
+                    parent[a] = b;
+                    total += weight;
+                    joined += 1;
+                }
+            }
+
+            assert_eq!(forest.total_weight(), total);
+            assert_eq!(forest.edges().len(), joined);
+            assert_eq!(forest.trees(), nodes - joined);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Comparators that define the order in which a [`FibonacciHeap`] yields elements.
+//!
+//! The heap always keeps the element that compares as [`Ordering::Less`] than
+//! every other element at the front, so [`MinOrder`] produces a min-heap and
+//! [`MaxOrder`] produces a max-heap.
+//!
+//! [`FibonacciHeap`]: crate::FibonacciHeap
+
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A total order over elements of type `T`
+///
+/// # Examples
+/// ```
+/// use fibonacci_heap::{Compare, FibonacciHeap};
+/// use std::cmp::Ordering;
+///
+/// struct ByLength;
+///
+/// impl Compare<String> for ByLength {
+///     fn compare(&self, a: &String, b: &String) -> Ordering {
+///         a.len().cmp(&b.len())
+///     }
+/// }
+///
+/// let mut heap = FibonacciHeap::with_comparator(ByLength);
+/// heap.insert("three".to_string()).unwrap();
+/// heap.insert("one".to_string()).unwrap();
+/// assert_eq!(heap.extract_min(), Some("one".to_string()));
+/// ```
+pub trait Compare<T: ?Sized> {
+    /// Whether elements that compare equal leave the heap in insertion order
+    ///
+    /// Set by [`Stable`]; the heap then records a sequence number for every
+    /// inserted element and uses it to break ties.
+    const STABLE: bool = false;
+
+    /// Compares two elements; the heap extracts the `Less` one first
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Orders elements by their `Ord` implementation, smallest first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MinOrder;
+
+impl<T: Ord + ?Sized> Compare<T> for MinOrder {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Orders elements by their `Ord` implementation, largest first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MaxOrder;
+
+impl<T: Ord + ?Sized> Compare<T> for MaxOrder {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+/// Orders elements with a closure returning an `Ordering`
+#[derive(Clone, Copy)]
+pub struct FnComparator<F>(pub F);
+
+impl<T: ?Sized, F: Fn(&T, &T) -> Ordering> Compare<T> for FnComparator<F> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a, b)
+    }
+}
+
+impl<F> fmt::Debug for FnComparator<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnComparator").finish_non_exhaustive()
+    }
+}
+
+/// Orders elements by a key extracted with a closure, smallest key first
+#[derive(Clone, Copy)]
+pub struct KeyComparator<F>(pub F);
+
+impl<T: ?Sized, K: Ord, F: Fn(&T) -> K> Compare<T> for KeyComparator<F> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a).cmp(&(self.0)(b))
+    }
+}
+
+impl<F> fmt::Debug for KeyComparator<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyComparator").finish_non_exhaustive()
+    }
+}
+
+/// Makes a comparator stable: equal elements are extracted in insertion order
+///
+/// Insertion order is global, so it carries over when stable heaps are merged.
+/// Changing a key does not change an element's place among its equals.
+///
+/// # Examples
+/// ```
+/// use fibonacci_heap::{FibonacciHeap, KeyComparator, Stable};
+///
+/// // Jobs are ordered by priority only; equal priorities run first come, first served
+/// let mut heap = FibonacciHeap::with_comparator(Stable(KeyComparator(|job: &(u8, char)| job.0)));
+/// for job in [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')] {
+///     heap.insert(job).unwrap();
+/// }
+///
+/// assert_eq!(heap.into_sorted_vec(), vec![(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Stable<C = MinOrder>(pub C);
+
+impl<T: ?Sized, C: Compare<T>> Compare<T> for Stable<C> {
+    const STABLE: bool = true;
+
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self.0.compare(a, b)
+    }
+}
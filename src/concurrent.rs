@@ -0,0 +1,226 @@
+//! A thread-safe Fibonacci Heap, available with the `concurrent` feature.
+
+use std::sync::{Mutex, MutexGuard};
+
+use crate::{Compare, FibonacciHeap, HeapError, MaxOrder, MinOrder, NodeHandle};
+
+/// A [`FibonacciHeap`] that can be shared between threads
+///
+/// Every operation takes `&self` and runs under an internal lock, so the heap
+/// can be wrapped in an `Arc` and used from many threads at once. The
+/// [`NodeHandle`]s it returns are plain `Copy` values and may be sent to
+/// other threads freely.
+///
+/// # Panics
+/// Operations panic if another thread panicked while holding the lock, as
+/// the heap may have been left half-restructured.
+///
+/// # Example
+/// ```
+/// use fibonacci_heap::ConcurrentFibonacciHeap;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let heap = Arc::new(ConcurrentFibonacciHeap::new());
+/// let workers: Vec<_> = (0..4)
+///     .map(|i| {
+///         let heap = Arc::clone(&heap);
+///         thread::spawn(move || heap.insert(i).unwrap())
+///     })
+///     .collect();
+/// for worker in workers {
+///     worker.join().unwrap();
+/// }
+///
+/// assert_eq!(heap.len(), 4);
+/// assert_eq!(heap.extract_min(), Some(0));
+/// ```
+#[derive(Debug)]
+pub struct ConcurrentFibonacciHeap<T, C = MinOrder> {
+    inner: Mutex<FibonacciHeap<T, C>>,
+}
+
+impl<T, C: Compare<T> + Default> Default for ConcurrentFibonacciHeap<T, C> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T, C> From<FibonacciHeap<T, C>> for ConcurrentFibonacciHeap<T, C> {
+    fn from(heap: FibonacciHeap<T, C>) -> Self {
+        ConcurrentFibonacciHeap {
+            inner: Mutex::new(heap),
+        }
+    }
+}
+
+impl<T: Ord> ConcurrentFibonacciHeap<T> {
+    /// Creates a new empty thread-safe min-heap
+    pub fn new() -> Self {
+        Self::with_comparator(MinOrder)
+    }
+}
+
+impl<T: Ord> ConcurrentFibonacciHeap<T, MaxOrder> {
+    /// Creates a new empty thread-safe max-heap
+    pub fn new_max() -> Self {
+        Self::with_comparator(MaxOrder)
+    }
+}
+
+impl<T, C: Compare<T>> ConcurrentFibonacciHeap<T, C> {
+    /// Creates a new empty thread-safe heap ordered by the given comparator
+    pub fn with_comparator(comparator: C) -> Self {
+        FibonacciHeap::with_comparator(comparator).into()
+    }
+
+    /// Inserts a new key and returns a handle to the created node
+    pub fn insert(&self, key: T) -> Result<NodeHandle, HeapError> {
+        self.lock().insert(key)
+    }
+
+    /// Extracts the minimum value from the heap
+    pub fn extract_min(&self) -> Option<T> {
+        self.lock().extract_min()
+    }
+
+    /// Deletes an arbitrary node from the heap and returns its key
+    pub fn delete(&self, node: &NodeHandle) -> Result<T, HeapError> {
+        self.lock().delete(node)
+    }
+
+    /// Decreases the key of a node
+    pub fn decrease_key(&self, node: &NodeHandle, new_key: T) -> Result<(), HeapError> {
+        self.lock().decrease_key(node, new_key)
+    }
+
+    /// Increases the key of a node
+    pub fn increase_key(&self, node: &NodeHandle, new_key: T) -> Result<(), HeapError> {
+        self.lock().increase_key(node, new_key)
+    }
+
+    /// Changes the key of a node in either direction
+    pub fn update_key(&self, node: &NodeHandle, new_key: T) -> Result<(), HeapError> {
+        self.lock().update_key(node, new_key)
+    }
+
+    /// Merges a heap into this one; handles issued by `other` remain valid
+    pub fn merge(&self, other: FibonacciHeap<T, C>) {
+        self.lock().merge(other);
+    }
+
+    /// Checks if the heap is empty
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Returns the number of nodes in the heap
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Clears the heap, removing all values
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Runs several operations on the underlying heap under a single lock
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::ConcurrentFibonacciHeap;
+    ///
+    /// let heap = ConcurrentFibonacciHeap::new();
+    /// let pair = heap.with_heap(|heap| (heap.extract_min(), heap.insert(7).unwrap()));
+    /// assert_eq!(pair.0, None);
+    /// ```
+    pub fn with_heap<R>(&self, f: impl FnOnce(&mut FibonacciHeap<T, C>) -> R) -> R {
+        f(&mut self.lock())
+    }
+
+    /// Consumes the wrapper and returns the underlying heap
+    pub fn into_inner(self) -> FibonacciHeap<T, C> {
+        self.inner.into_inner().expect(POISONED)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, FibonacciHeap<T, C>> {
+        self.inner.lock().expect(POISONED)
+    }
+}
+
+impl<T: Clone, C: Compare<T>> ConcurrentFibonacciHeap<T, C> {
+    /// Returns a copy of the minimum value without removing it
+    pub fn peek_min(&self) -> Option<T> {
+        self.lock().peek_min()
+    }
+}
+
+const POISONED: &str = "a thread panicked while holding the heap lock";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn assert_send_sync<S: Send + Sync>() {}
+
+    #[test]
+    fn test_heap_and_handles_are_thread_safe() {
+        assert_send_sync::<ConcurrentFibonacciHeap<String>>();
+        assert_send_sync::<ConcurrentFibonacciHeap<i32, MaxOrder>>();
+        assert_send_sync::<NodeHandle>();
+    }
+
+    #[test]
+    fn test_concurrent_insert_decrease_extract() {
+        let heap = ConcurrentFibonacciHeap::new();
+
+        // Each thread inserts its own range and keeps the handles
+        let handles: Vec<Vec<NodeHandle>> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..4)
+                .map(|t| {
+                    let heap = &heap;
+                    scope.spawn(move || {
+                        (0..250)
+                            .map(|i| heap.insert(1000 + t * 250 + i).unwrap())
+                            .collect()
+                    })
+                })
+                .collect();
+            workers.into_iter().map(|w| w.join().unwrap()).collect()
+        });
+        assert_eq!(heap.len(), 1000);
+
+        // Handles are decreased from threads other than the one that created them
+        thread::scope(|scope| {
+            for (t, chunk) in handles.iter().rev().enumerate() {
+                let heap = &heap;
+                scope.spawn(move || {
+                    for (i, handle) in chunk.iter().enumerate().take(10) {
+                        heap.decrease_key(handle, (t * 10 + i) as i32).unwrap();
+                    }
+                });
+            }
+        });
+
+        let drained: Vec<_> = std::iter::from_fn(|| heap.extract_min()).collect();
+        assert_eq!(drained.len(), 1000);
+        assert_eq!(&drained[..40], &(0..40).collect::<Vec<_>>()[..]);
+        assert!(drained.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_merge_and_into_inner() {
+        let heap = ConcurrentFibonacciHeap::new_max();
+        heap.insert(3).unwrap();
+
+        let mut other = FibonacciHeap::new_max();
+        let node = other.insert(1).unwrap();
+        heap.merge(other);
+        heap.update_key(&node, 9).unwrap();
+
+        let mut inner = heap.into_inner();
+        assert_eq!(inner.extract_min(), Some(9));
+        assert_eq!(inner.extract_min(), Some(3));
+    }
+}
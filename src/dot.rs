@@ -0,0 +1,138 @@
+//! Graphviz export of the heap forest, for debugging.
+
+use std::fmt::Write;
+
+use crate::{Compare, FibonacciHeap, NodeRef};
+
+impl<T, C: Compare<T>> FibonacciHeap<T, C> {
+    /// Renders the heap's trees as a Graphviz DOT document
+    ///
+    /// Each node is labelled with `label(key)` and its degree. Roots share a
+    /// rank and are chained by dashed edges in root list order, solid edges
+    /// point from parents to children, marked nodes are filled, and the
+    /// minimum is drawn with a double outline and a `min` pointer.
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap: FibonacciHeap<_> = (1..=5).collect();
+    /// heap.extract_min();
+    ///
+    /// let dot = heap.to_dot(|key| key.to_string());
+    /// assert!(dot.starts_with("digraph FibonacciHeap {"));
+    /// // Render with `dot -Tsvg heap.dot -o heap.svg`
+    /// ```
+    pub fn to_dot(&self, label: impl Fn(&T) -> String) -> String {
+        let mut dot = String::from("digraph FibonacciHeap {\n    node [shape=circle];\n");
+        if let Some(min) = self.min {
+            let roots = self.ring_members(min);
+
+            let _ = writeln!(dot, "    min [shape=plaintext];");
+            let _ = writeln!(dot, "    min -> {};", dot_id(min));
+            let ids: Vec<_> = roots.iter().map(|&root| dot_id(root)).collect();
+            let _ = writeln!(dot, "    {{ rank=same; {}; }}", ids.join("; "));
+            for pair in ids.windows(2) {
+                let _ = writeln!(
+                    dot,
+                    "    {} -> {} [style=dashed, arrowhead=none];",
+                    pair[0], pair[1]
+                );
+            }
+
+            let mut pending = roots;
+            while let Some(at) = pending.pop() {
+                let node = self.node(at);
+                let mut attributes = format!(
+                    "label=\"{}\\ndeg {}\"",
+                    escape(&label(&node.key)),
+                    node.degree
+                );
+                if node.marked {
+                    attributes.push_str(", style=filled, fillcolor=lightcoral");
+                }
+                if Some(at) == self.min {
+                    attributes.push_str(", shape=doublecircle");
+                }
+                let _ = writeln!(dot, "    {} [{}];", dot_id(at), attributes);
+
+                if let Some(child) = self.first_child(at) {
+                    for child in self.ring_members(child) {
+                        let _ = writeln!(dot, "    {} -> {};", dot_id(at), dot_id(child));
+                        pending.push(child);
+                    }
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// A DOT identifier unique to the node's position in the arena
+fn dot_id(at: NodeRef) -> String {
+    format!("n{}_{}", at.segment, at.slot)
+}
+
+/// Escapes a label for use inside a quoted DOT string
+fn escape(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for c in label.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::shaped_heap;
+
+    #[test]
+    fn test_dot_shows_structure() {
+        let (heap, handles) = shaped_heap();
+
+        let dot = heap.to_dot(|key| format!("k{key}"));
+        let min = dot_id(heap.resolve(&handles[40]).unwrap());
+        assert!(dot.contains(&format!("min -> {min};")));
+        let min_line = dot
+            .lines()
+            .find(|line| line.contains("shape=doublecircle"))
+            .unwrap();
+        assert!(min_line.starts_with(&format!("    {min} [label=\"k-1\\ndeg ")));
+
+        let marked = heap.segments[0]
+            .slots
+            .iter()
+            .filter(|slot| slot.node.as_ref().is_some_and(|node| node.marked))
+            .count();
+        assert!(marked > 0);
+        assert_eq!(dot.matches("fillcolor=lightcoral").count(), marked);
+
+        // Every node but the first root has exactly one incoming solid or dashed edge
+        let edges = dot
+            .lines()
+            .filter(|line| line.contains(" -> ") && !line.starts_with("    min"))
+            .count();
+        assert_eq!(edges + 1, heap.len());
+        assert_eq!(dot.matches("deg ").count(), heap.len());
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_dot_escapes_labels() {
+        let mut heap = FibonacciHeap::new();
+        heap.insert("say \"hi\"\\\nbye").unwrap();
+        let dot = heap.to_dot(|key| key.to_string());
+        assert!(dot.contains(r#"label="say \"hi\"\\\nbye\ndeg 0""#));
+        assert_eq!(
+            FibonacciHeap::<u8>::new().to_dot(|key| key.to_string()),
+            "digraph FibonacciHeap {\n    node [shape=circle];\n}\n"
+        );
+    }
+}
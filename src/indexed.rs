@@ -0,0 +1,352 @@
+//! A Fibonacci Heap addressed by caller-chosen keys instead of handles.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem;
+
+use crate::keyed::ByPriority;
+use crate::{Compare, FibonacciHeap, MaxOrder, MinOrder, NodeHandle};
+
+/// What [`IndexedFibonacciHeap::push_or_decrease`] did with the given priority
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PushOutcome {
+    /// The key was not queued and has been added
+    Inserted,
+    /// The key was queued and its priority has moved earlier
+    Decreased,
+    /// The key was queued with an equal or earlier priority, which was kept
+    Unchanged,
+}
+
+/// A priority queue of unique keys, each with a changeable priority
+///
+/// The heap owns the map from each key to the handle of its entry, so
+/// priorities are changed and entries removed by key. Keys are stored twice,
+/// in the map and in the heap, and therefore need to be `Clone`.
+///
+/// # Example
+/// ```
+/// use fibonacci_heap::IndexedFibonacciHeap;
+///
+/// let mut heap = IndexedFibonacciHeap::new();
+/// heap.push("write report", 10);
+/// heap.push("answer email", 5);
+///
+/// heap.change_priority("write report", 1);
+/// assert_eq!(heap.pop(), Some(("write report", 1)));
+/// assert!(!heap.contains("write report"));
+/// ```
+#[derive(Debug)]
+pub struct IndexedFibonacciHeap<K, P, C = MinOrder> {
+    heap: FibonacciHeap<(P, K), ByPriority<C>>,
+    handles: HashMap<K, NodeHandle>,
+}
+
+impl<K, P, C: Compare<P> + Default> Default for IndexedFibonacciHeap<K, P, C> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<K, P: Ord> IndexedFibonacciHeap<K, P> {
+    /// Creates a new empty heap that pops the smallest priority first
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::IndexedFibonacciHeap;
+    /// let heap = IndexedFibonacciHeap::<u32, i64>::new();
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::with_comparator(MinOrder)
+    }
+}
+
+impl<K, P: Ord> IndexedFibonacciHeap<K, P, MaxOrder> {
+    /// Creates a new empty heap that pops the largest priority first
+    pub fn new_max() -> Self {
+        Self::with_comparator(MaxOrder)
+    }
+}
+
+impl<K, P, C: Compare<P>> IndexedFibonacciHeap<K, P, C> {
+    /// Creates a new empty heap ordering priorities with the given comparator
+    pub fn with_comparator(comparator: C) -> Self {
+        IndexedFibonacciHeap {
+            heap: FibonacciHeap::with_comparator(ByPriority(comparator)),
+            handles: HashMap::new(),
+        }
+    }
+
+    /// Checks if the heap is empty
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns the number of keys in the heap
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Borrows the key with the minimum priority and its priority without removing them
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap
+            .peek_min_ref()
+            .map(|(priority, key)| (key, priority))
+    }
+
+    /// Clears the heap, removing all keys
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.handles.clear();
+    }
+}
+
+impl<K: Hash + Eq + Clone, P, C: Compare<P>> IndexedFibonacciHeap<K, P, C> {
+    /// Queues `key` with the given priority
+    ///
+    /// If `key` is already queued, its priority is changed instead and the
+    /// old priority is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::IndexedFibonacciHeap;
+    ///
+    /// let mut heap = IndexedFibonacciHeap::new();
+    /// assert_eq!(heap.push('a', 3), None);
+    /// assert_eq!(heap.push('a', 7), Some(3));
+    /// assert_eq!(heap.len(), 1);
+    /// ```
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if self.handles.contains_key(&key) {
+            return self.change_priority(&key, priority);
+        }
+        let handle = self
+            .heap
+            .insert((priority, key.clone()))
+            .expect("inserting into a heap cannot fail");
+        self.handles.insert(key, handle);
+        None
+    }
+
+    /// Queues `key`, or moves it earlier if `priority` comes before its current one
+    ///
+    /// This is the relaxation step of algorithms like Dijkstra's: a priority
+    /// that would not move the key earlier is dropped, and the returned
+    /// [`PushOutcome`] tells which case applied.
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::{IndexedFibonacciHeap, PushOutcome};
+    ///
+    /// let mut heap = IndexedFibonacciHeap::new();
+    /// assert_eq!(heap.push_or_decrease("b", 7), PushOutcome::Inserted);
+    /// assert_eq!(heap.push_or_decrease("b", 4), PushOutcome::Decreased);
+    /// assert_eq!(heap.push_or_decrease("b", 9), PushOutcome::Unchanged);
+    /// assert_eq!(heap.pop(), Some(("b", 4)));
+    /// ```
+    pub fn push_or_decrease(&mut self, key: K, priority: P) -> PushOutcome {
+        let Some(handle) = self.handles.get(&key) else {
+            self.push(key, priority);
+            return PushOutcome::Inserted;
+        };
+        let at = self
+            .heap
+            .resolve(handle)
+            .expect("indexed keys have live handles");
+        let current = &self.heap.node(at).key.0;
+        if self.heap.comparator.0.compare(&priority, current) != Ordering::Less {
+            return PushOutcome::Unchanged;
+        }
+        self.heap
+            .modify_key(handle, |_, entry| {
+                entry.0 = priority;
+                Ok(Ordering::Less)
+            })
+            .expect("indexed keys have live handles");
+        PushOutcome::Decreased
+    }
+
+    /// Removes and returns the key with the minimum priority, along with that priority
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        let (priority, key) = self.heap.extract_min()?;
+        self.handles.remove(&key);
+        Some((key, priority))
+    }
+
+    /// Changes the priority of `key` in either direction and returns the old priority
+    ///
+    /// Returns `None`, leaving the heap untouched, if `key` is not queued.
+    pub fn change_priority<Q>(&mut self, key: &Q, priority: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = self.handles.get(key)?;
+        let mut previous = None;
+        self.heap
+            .modify_key(handle, |comparator, entry| {
+                let direction = comparator.0.compare(&priority, &entry.0);
+                previous = Some(mem::replace(&mut entry.0, priority));
+                Ok(direction)
+            })
+            .expect("indexed keys have live handles");
+        previous
+    }
+
+    /// Removes `key` from the heap and returns it with its priority
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<(K, P)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = self.handles.remove(key)?;
+        let (priority, key) = self
+            .heap
+            .delete(&handle)
+            .expect("indexed keys have live handles");
+        Some((key, priority))
+    }
+
+    /// Checks whether `key` is queued
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.handles.contains_key(key)
+    }
+
+    /// Returns the current priority of `key`, if it is queued
+    pub fn priority<Q>(&self, key: &Q) -> Option<&P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let at = self
+            .heap
+            .resolve(self.handles.get(key)?)
+            .expect("indexed keys have live handles");
+        Some(&self.heap.node(at).key.0)
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Clone, C: Clone> Clone for IndexedFibonacciHeap<K, P, C> {
+    fn clone(&self) -> Self {
+        // The copy has a new identity, so every handle in the map must be remapped
+        let (heap, remap) = self.heap.clone_with_handle_map();
+        let handles = self
+            .handles
+            .iter()
+            .map(|(key, handle)| (key.clone(), remap[handle]))
+            .collect();
+        IndexedFibonacciHeap { heap, handles }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the heap invariants and that the map and the heap agree
+    fn assert_consistent<K: Hash + Eq + Clone, P, C: Compare<P>>(
+        heap: &IndexedFibonacciHeap<K, P, C>,
+    ) {
+        assert_eq!(heap.heap.validate(), Ok(()));
+        assert_eq!(heap.handles.len(), heap.heap.len());
+        for (key, handle) in &heap.handles {
+            let at = heap.heap.resolve(handle).unwrap();
+            assert!(heap.heap.node(at).key.1 == *key);
+        }
+    }
+
+    #[test]
+    fn test_push_pop_and_remove() {
+        let mut heap = IndexedFibonacciHeap::new();
+        for id in 0..20u32 {
+            assert_eq!(heap.push(id, 100 - id as i64), None);
+        }
+        assert_eq!(heap.peek(), Some((&19, &81)));
+        assert_eq!(heap.pop(), Some((19, 81)));
+        assert!(!heap.contains(&19));
+
+        assert_eq!(heap.remove(&5), Some((5, 95)));
+        assert_eq!(heap.remove(&5), None);
+        assert_eq!(heap.priority(&6), Some(&94));
+        assert_eq!(heap.priority(&5), None);
+        assert_eq!(heap.len(), 18);
+        assert_consistent(&heap);
+
+        let order: Vec<_> = std::iter::from_fn(|| heap.pop())
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(order.len(), 18);
+        assert!(order.windows(2).all(|pair| pair[0] > pair[1]));
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_change_priority() {
+        let mut heap = IndexedFibonacciHeap::new();
+        for name in ["a", "b", "c", "d", "e", "f", "g", "h"] {
+            heap.push(name.to_string(), name.as_bytes()[0]);
+        }
+        heap.pop();
+
+        // Lookups take borrowed keys
+        assert_eq!(heap.change_priority("h", 0), Some(b'h'));
+        assert_eq!(heap.change_priority("b", b'z'), Some(b'b'));
+        assert_eq!(heap.change_priority("c", b'c'), Some(b'c'));
+        assert_eq!(heap.change_priority("a", 0), None);
+        assert_eq!(heap.push("d".to_string(), 1), Some(b'd'));
+        assert_consistent(&heap);
+
+        let order: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(order[0], ("h".to_string(), 0));
+        assert_eq!(order[1], ("d".to_string(), 1));
+        assert_eq!(order.last(), Some(&("b".to_string(), b'z')));
+    }
+
+    #[test]
+    fn test_push_or_decrease() {
+        let mut heap = IndexedFibonacciHeap::new_max();
+        for id in 0..16 {
+            assert_eq!(heap.push_or_decrease(id, id * 10), PushOutcome::Inserted);
+        }
+        heap.pop();
+
+        // Under a max order, "decrease" means moving towards the front
+        assert_eq!(heap.push_or_decrease(2, 500), PushOutcome::Decreased);
+        assert_eq!(heap.push_or_decrease(3, 30), PushOutcome::Unchanged);
+        assert_eq!(heap.push_or_decrease(4, 0), PushOutcome::Unchanged);
+        assert_eq!(heap.priority(&4), Some(&40));
+        assert_eq!(heap.push_or_decrease(15, 1), PushOutcome::Inserted);
+        assert_consistent(&heap);
+
+        assert_eq!(heap.pop(), Some((2, 500)));
+        assert_eq!(heap.pop(), Some((14, 140)));
+        assert_eq!(heap.len(), 14);
+    }
+
+    #[test]
+    fn test_clone_keeps_index() {
+        let mut heap = IndexedFibonacciHeap::new_max();
+        for id in 0..32 {
+            heap.push(id, id);
+        }
+        heap.pop();
+
+        let mut copy = heap.clone();
+        assert_eq!(copy.change_priority(&3, 100), Some(3));
+        assert_eq!(copy.remove(&30), Some((30, 30)));
+        assert_consistent(&copy);
+        assert_eq!(copy.pop(), Some((3, 100)));
+        assert_eq!(heap.pop(), Some((30, 30)));
+
+        heap.clear();
+        assert!(heap.is_empty() && !heap.contains(&3));
+        assert_eq!(heap.push(3, 3), None);
+    }
+}
@@ -0,0 +1,457 @@
+//! Iterators over the elements of a [`FibonacciHeap`], and building heaps from them.
+//!
+//! Apart from [`DrainSorted`] and [`FibonacciHeap::into_sorted_vec`], the
+//! iterators visit elements in arbitrary order by walking the node storage
+//! directly, without touching the tree structure.
+
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::{slice, vec};
+
+use crate::{Compare, FibonacciHeap, NodeHandle, Segment, Slot};
+
+/// An iterator over references to the elements of a heap, in arbitrary order
+///
+/// Created by [`FibonacciHeap::iter`].
+#[derive(Debug)]
+pub struct Iter<'a, T> {
+    segments: slice::Iter<'a, Segment<T>>,
+    slots: slice::Iter<'a, Slot<T>>,
+    remaining: usize,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            segments: self.segments.clone(),
+            slots: self.slots.clone(),
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            match self.slots.next() {
+                Some(Slot {
+                    node: Some(node), ..
+                }) => {
+                    self.remaining -= 1;
+                    return Some(&node.key);
+                }
+                Some(_) => {}
+                None => self.slots = self.segments.next()?.slots.iter(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An owning iterator over the elements of a heap, in arbitrary order
+///
+/// Created by the [`IntoIterator`] implementation of [`FibonacciHeap`].
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    segments: vec::IntoIter<Segment<T>>,
+    slots: vec::IntoIter<Slot<T>>,
+    remaining: usize,
+}
+
+impl<T> IntoIter<T> {
+    fn new(segments: Vec<Segment<T>>, remaining: usize) -> Self {
+        IntoIter {
+            segments: segments.into_iter(),
+            slots: Vec::new().into_iter(),
+            remaining,
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            match self.slots.next() {
+                Some(Slot {
+                    node: Some(node), ..
+                }) => {
+                    self.remaining -= 1;
+                    return Some(node.key);
+                }
+                Some(_) => {}
+                None => self.slots = self.segments.next()?.slots.into_iter(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// A draining iterator over the elements of a heap, in arbitrary order
+///
+/// Created by [`FibonacciHeap::drain`]. The heap is already empty while the
+/// iterator is alive, and elements not consumed are dropped with it.
+#[derive(Debug)]
+pub struct Drain<'a, T> {
+    iter: IntoIter<T>,
+    heap: PhantomData<&'a mut T>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
+/// A draining iterator over the elements of a heap, in heap order
+///
+/// Created by [`FibonacciHeap::drain_sorted`]. Each call to `next` performs
+/// an [`extract_min`](FibonacciHeap::extract_min); dropping the iterator
+/// early clears the rest of the heap.
+#[derive(Debug)]
+pub struct DrainSorted<'a, T, C: Compare<T>> {
+    heap: &'a mut FibonacciHeap<T, C>,
+}
+
+impl<T, C: Compare<T>> Iterator for DrainSorted<'_, T, C> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.extract_min()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<T, C: Compare<T>> ExactSizeIterator for DrainSorted<'_, T, C> {}
+
+impl<T, C: Compare<T>> FusedIterator for DrainSorted<'_, T, C> {}
+
+impl<T, C: Compare<T>> Drop for DrainSorted<'_, T, C> {
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+
+impl<T, C> FibonacciHeap<T, C> {
+    /// Returns an iterator visiting all elements in arbitrary order
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// heap.insert(3).unwrap();
+    /// heap.insert(1).unwrap();
+    ///
+    /// let mut seen: Vec<_> = heap.iter().copied().collect();
+    /// seen.sort();
+    /// assert_eq!(seen, vec![1, 3]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            segments: self.segments.iter(),
+            slots: [].iter(),
+            remaining: self.node_count,
+        }
+    }
+}
+
+impl<T, C: Compare<T>> FibonacciHeap<T, C> {
+    /// Removes all elements and returns them in arbitrary order
+    ///
+    /// Handles issued before the call are no longer valid afterwards.
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// heap.insert(2).unwrap();
+    /// heap.insert(1).unwrap();
+    ///
+    /// assert_eq!(heap.drain().count(), 2);
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        let remaining = self.node_count;
+        Drain {
+            iter: IntoIter::new(self.take_segments(), remaining),
+            heap: PhantomData,
+        }
+    }
+
+    /// Removes all elements and returns them in the order `extract_min` would
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// for key in [5, 1, 3] {
+    ///     heap.insert(key).unwrap();
+    /// }
+    ///
+    /// let first_two: Vec<_> = heap.drain_sorted().take(2).collect();
+    /// assert_eq!(first_two, vec![1, 3]);
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, C> {
+        DrainSorted { heap: self }
+    }
+
+    /// Consumes the heap and returns its elements in the order `extract_min` would
+    ///
+    /// For a min-heap this is ascending order, for a max-heap descending.
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new_max();
+    /// for key in [2, 7, 4] {
+    ///     heap.insert(key).unwrap();
+    /// }
+    ///
+    /// assert_eq!(heap.into_sorted_vec(), vec![7, 4, 2]);
+    /// ```
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::with_capacity(self.len());
+        sorted.extend(self.drain_sorted());
+        sorted
+    }
+
+    /// Consumes the heap and returns its elements in arbitrary order
+    pub fn into_vec(self) -> Vec<T> {
+        self.into_iter().collect()
+    }
+}
+
+impl<T, C: Compare<T> + Default> FibonacciHeap<T, C> {
+    /// Builds a heap from an iterator and returns the handles in insertion order
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let (mut heap, handles) = FibonacciHeap::<i32>::from_iter_with_handles(vec![30, 10, 20]);
+    /// heap.decrease_key(&handles[0], 5).unwrap();
+    /// assert_eq!(heap.extract_min(), Some(5));
+    /// ```
+    pub fn from_iter_with_handles<I: IntoIterator<Item = T>>(keys: I) -> (Self, Vec<NodeHandle>) {
+        let mut heap = Self::default();
+        let handles = heap.extend_with_handles(keys);
+        (heap, handles)
+    }
+}
+
+impl<T, C: Compare<T> + Default> FromIterator<T> for FibonacciHeap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(keys: I) -> Self {
+        let mut heap = Self::default();
+        heap.extend(keys);
+        heap
+    }
+}
+
+impl<T, C: Compare<T> + Default> From<Vec<T>> for FibonacciHeap<T, C> {
+    fn from(keys: Vec<T>) -> Self {
+        keys.into_iter().collect()
+    }
+}
+
+impl<T, C: Compare<T> + Default, const N: usize> From<[T; N]> for FibonacciHeap<T, C> {
+    fn from(keys: [T; N]) -> Self {
+        keys.into_iter().collect()
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for FibonacciHeap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, keys: I) {
+        self.bulk_insert(keys.into_iter(), |_| {});
+    }
+}
+
+impl<'a, T: Copy + 'a, C: Compare<T>> Extend<&'a T> for FibonacciHeap<T, C> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, keys: I) {
+        self.extend(keys.into_iter().copied());
+    }
+}
+
+impl<T, C> IntoIterator for FibonacciHeap<T, C> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter::new(self.segments, self.node_count)
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a FibonacciHeap<T, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FibonacciHeap, HeapError, MaxOrder};
+
+    /// Builds a heap whose nodes are spread over merged segments and trees
+    fn scattered_heap() -> FibonacciHeap<i32> {
+        let mut heap = FibonacciHeap::new();
+        let mut other = FibonacciHeap::new();
+        for i in 0..20 {
+            heap.insert(i * 2).unwrap();
+            other.insert(i * 2 + 1).unwrap();
+        }
+        heap.merge(other);
+        assert_eq!(heap.extract_min(), Some(0));
+        heap
+    }
+
+    #[test]
+    fn test_iter_visits_every_element() {
+        let mut heap = scattered_heap();
+        let node = heap.insert(100).unwrap();
+        heap.delete(&node).unwrap();
+
+        let iter = heap.iter();
+        assert_eq!(iter.len(), 39);
+        let mut seen: Vec<_> = iter.copied().collect();
+        seen.sort();
+        assert_eq!(seen, (1..40).collect::<Vec<_>>());
+        assert_eq!((&heap).into_iter().count(), 39);
+    }
+
+    #[test]
+    fn test_into_iter_and_into_vec() {
+        let mut owned: Vec<_> = scattered_heap().into_iter().collect();
+        owned.sort();
+        assert_eq!(owned, (1..40).collect::<Vec<_>>());
+
+        let heap: FibonacciHeap<String> = FibonacciHeap::new();
+        assert!(heap.into_vec().is_empty());
+    }
+
+    #[test]
+    fn test_drain_empties_heap_and_invalidates_handles() {
+        let mut heap = FibonacciHeap::new();
+        let node = heap.insert(String::from("kept")).unwrap();
+        heap.insert(String::from("dropped")).unwrap();
+
+        let mut drain = heap.drain();
+        assert_eq!(drain.len(), 2);
+        drain.next().unwrap();
+        drop(drain);
+
+        assert!(heap.is_empty());
+        assert_eq!(
+            heap.decrease_key(&node, String::new()),
+            Err(HeapError::NodeNotFound)
+        );
+        heap.insert(String::from("again")).unwrap();
+        assert_eq!(heap.len(), 1);
+    }
+
+    #[test]
+    fn test_drain_sorted_and_into_sorted_vec() {
+        let mut heap = scattered_heap();
+        let prefix: Vec<_> = heap.drain_sorted().take(3).collect();
+        assert_eq!(prefix, vec![1, 2, 3]);
+        assert!(heap.is_empty());
+
+        assert_eq!(
+            scattered_heap().into_sorted_vec(),
+            (1..40).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_collect_and_extend() {
+        let mut heap: FibonacciHeap<i32> = (0..100).rev().collect();
+        assert_eq!(heap.len(), 100);
+        assert_eq!(heap.peek_min(), Some(0));
+
+        heap.extend(&[-5, 50]);
+        heap.extend(Vec::<i32>::new());
+        assert_eq!(heap.len(), 102);
+        assert_eq!(heap.extract_min(), Some(-5));
+        assert_eq!(heap.extract_min(), Some(0));
+
+        let max: FibonacciHeap<_, MaxOrder> = FibonacciHeap::from(vec![3, 9, 1]);
+        assert_eq!(max.into_sorted_vec(), vec![9, 3, 1]);
+        assert_eq!(FibonacciHeap::<_>::from([2, 1]).peek_min(), Some(1));
+    }
+
+    #[test]
+    fn test_bulk_handles_follow_insertion_order() {
+        let (mut heap, handles) = FibonacciHeap::<i32>::from_iter_with_handles(10..20);
+        assert_eq!(handles.len(), 10);
+        for (offset, handle) in handles.iter().enumerate() {
+            heap.decrease_key(handle, -(offset as i32)).unwrap();
+        }
+        assert_eq!(heap.extract_min(), Some(-9));
+
+        // Slots freed by the extraction are reused by the next bulk insert
+        let more = heap.extend_with_handles([7, 8]);
+        assert_eq!(heap.delete(&more[1]), Ok(8));
+        assert_eq!(heap.delete(&handles[9]), Err(HeapError::NodeNotFound));
+        assert_eq!(
+            heap.into_sorted_vec(),
+            vec![-8, -7, -6, -5, -4, -3, -2, -1, 0, 7]
+        );
+    }
+
+    #[test]
+    fn test_bulk_insert_survives_panicking_iterator() {
+        let mut heap: FibonacciHeap<i32> = vec![50, 60].into();
+        let keys = (0..10)
+            .rev()
+            .inspect(|&key| assert!(key > 4, "iterator gave up"));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| heap.extend(keys)));
+        assert!(result.is_err());
+
+        // The keys produced before the panic are all in the heap
+        heap.validate().unwrap();
+        assert_eq!(heap.len(), 7);
+        assert_eq!(heap.into_sorted_vec(), vec![5, 6, 7, 8, 9, 50, 60]);
+    }
+}
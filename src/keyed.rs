@@ -0,0 +1,313 @@
+//! A Fibonacci Heap that keeps priorities separate from their payloads.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::{Compare, FibonacciHeap, HeapError, MaxOrder, MinOrder, NodeHandle, Stable};
+
+/// Orders `(priority, value)` entries by their priority only
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ByPriority<C>(pub(crate) C);
+
+impl<P, V, C: Compare<P>> Compare<(P, V)> for ByPriority<C> {
+    const STABLE: bool = C::STABLE;
+
+    fn compare(&self, a: &(P, V), b: &(P, V)) -> Ordering {
+        self.0.compare(&a.0, &b.0)
+    }
+}
+
+/// A Fibonacci Heap storing `(priority, value)` pairs
+///
+/// Only the priority is compared and updated, so the value never needs to
+/// implement `Ord` or `Clone` and is moved out on extraction.
+///
+/// # Example
+/// ```
+/// use fibonacci_heap::KeyedFibonacciHeap;
+///
+/// let mut heap = KeyedFibonacciHeap::new();
+/// let write = heap.insert(10, "write report").unwrap();
+/// heap.insert(5, "answer email").unwrap();
+///
+/// heap.decrease_key(&write, 1).unwrap();
+/// assert_eq!(heap.extract_min(), Some((1, "write report")));
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyedFibonacciHeap<P, V, C = MinOrder> {
+    heap: FibonacciHeap<(P, V), ByPriority<C>>,
+}
+
+impl<P, V, C: Compare<P> + Default> Default for KeyedFibonacciHeap<P, V, C> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<P: Ord, V> KeyedFibonacciHeap<P, V> {
+    /// Creates a new empty heap that extracts the smallest priority first
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::KeyedFibonacciHeap;
+    /// let heap = KeyedFibonacciHeap::<i32, String>::new();
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::with_comparator(MinOrder)
+    }
+}
+
+impl<P: Ord, V> KeyedFibonacciHeap<P, V, MaxOrder> {
+    /// Creates a new empty heap that extracts the largest priority first
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::KeyedFibonacciHeap;
+    ///
+    /// let mut heap = KeyedFibonacciHeap::new_max();
+    /// heap.insert(1, "low").unwrap();
+    /// heap.insert(9, "high").unwrap();
+    /// assert_eq!(heap.extract_min(), Some((9, "high")));
+    /// ```
+    pub fn new_max() -> Self {
+        Self::with_comparator(MaxOrder)
+    }
+}
+
+impl<P: Ord, V> KeyedFibonacciHeap<P, V, Stable> {
+    /// Creates a new empty heap that extracts equal priorities in insertion order
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::KeyedFibonacciHeap;
+    ///
+    /// let mut heap = KeyedFibonacciHeap::new_stable();
+    /// heap.insert(1, "first").unwrap();
+    /// heap.insert(1, "second").unwrap();
+    /// assert_eq!(heap.extract_min(), Some((1, "first")));
+    /// ```
+    pub fn new_stable() -> Self {
+        Self::with_comparator(Stable(MinOrder))
+    }
+}
+
+impl<P, V, C: Compare<P>> KeyedFibonacciHeap<P, V, C> {
+    /// Creates a new empty heap ordering priorities with the given comparator
+    pub fn with_comparator(comparator: C) -> Self {
+        KeyedFibonacciHeap {
+            heap: FibonacciHeap::with_comparator(ByPriority(comparator)),
+        }
+    }
+
+    /// Inserts a value with the given priority and returns a handle to it
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::KeyedFibonacciHeap;
+    /// let mut heap = KeyedFibonacciHeap::new();
+    /// let node = heap.insert(3, vec![0u8; 1024]).unwrap();
+    /// ```
+    pub fn insert(&mut self, priority: P, value: V) -> Result<NodeHandle, HeapError> {
+        self.heap.insert((priority, value))
+    }
+
+    /// Removes and returns the entry with the minimum priority
+    pub fn extract_min(&mut self) -> Option<(P, V)> {
+        self.heap.extract_min()
+    }
+
+    /// Removes the entry behind a handle and returns it
+    pub fn delete(&mut self, node: &NodeHandle) -> Result<(P, V), HeapError> {
+        self.heap.delete(node)
+    }
+
+    /// Lowers the priority of an entry, leaving its value untouched
+    ///
+    /// Returns `HeapError::InvalidKey` if the new priority comes after the
+    /// current one in the heap order.
+    pub fn decrease_key(&mut self, node: &NodeHandle, priority: P) -> Result<(), HeapError> {
+        self.heap.modify_key(node, |comparator, entry| {
+            if comparator.0.compare(&priority, &entry.0) == Ordering::Greater {
+                return Err(HeapError::InvalidKey);
+            }
+            entry.0 = priority;
+            Ok(Ordering::Less)
+        })
+    }
+
+    /// Raises the priority of an entry, leaving its value untouched
+    ///
+    /// Returns `HeapError::InvalidKey` if the new priority comes before the
+    /// current one in the heap order.
+    pub fn increase_key(&mut self, node: &NodeHandle, priority: P) -> Result<(), HeapError> {
+        self.heap.modify_key(node, |comparator, entry| {
+            if comparator.0.compare(&priority, &entry.0) == Ordering::Less {
+                return Err(HeapError::InvalidKey);
+            }
+            entry.0 = priority;
+            Ok(Ordering::Greater)
+        })
+    }
+
+    /// Changes the priority of an entry in either direction
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::KeyedFibonacciHeap;
+    ///
+    /// let mut heap = KeyedFibonacciHeap::new();
+    /// let a = heap.insert(1, 'a').unwrap();
+    /// heap.insert(2, 'b').unwrap();
+    ///
+    /// heap.update_key(&a, 3).unwrap();
+    /// assert_eq!(heap.extract_min(), Some((2, 'b')));
+    /// ```
+    pub fn update_key(&mut self, node: &NodeHandle, priority: P) -> Result<(), HeapError> {
+        self.heap.modify_key(node, |comparator, entry| {
+            let direction = comparator.0.compare(&priority, &entry.0);
+            entry.0 = priority;
+            Ok(direction)
+        })
+    }
+
+    /// Merges another keyed heap into this one
+    pub fn merge(&mut self, other: KeyedFibonacciHeap<P, V, C>) {
+        self.heap.merge(other.heap);
+    }
+
+    /// Borrows the entry with the minimum priority without removing it
+    pub fn peek_min_ref(&self) -> Option<&(P, V)> {
+        self.heap.peek_min_ref()
+    }
+
+    /// Checks if the heap is empty
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns the number of entries in the heap
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Clears the heap, removing all entries
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+}
+
+impl<P: Clone, V: Clone, C: Compare<P>> KeyedFibonacciHeap<P, V, C> {
+    /// Returns a copy of the entry with the minimum priority without removing it
+    pub fn peek_min(&self) -> Option<(P, V)> {
+        self.heap.peek_min()
+    }
+}
+
+impl<P: Clone, V: Clone, C: Clone> KeyedFibonacciHeap<P, V, C> {
+    /// Creates a deep copy of the heap and maps every live handle to its copy
+    ///
+    /// See [`FibonacciHeap::clone_with_handle_map`].
+    pub fn clone_with_handle_map(&self) -> (Self, HashMap<NodeHandle, NodeHandle>) {
+        let (heap, handles) = self.heap.clone_with_handle_map();
+        (KeyedFibonacciHeap { heap }, handles)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<P: serde::Serialize, V: serde::Serialize, C: Compare<P>> serde::Serialize
+    for KeyedFibonacciHeap<P, V, C>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.heap.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, P, V, C> serde::Deserialize<'de> for KeyedFibonacciHeap<P, V, C>
+where
+    P: serde::Deserialize<'de>,
+    V: serde::Deserialize<'de>,
+    C: Compare<P> + Default,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::deserialize_with_handles(deserializer).map(|(heap, _)| heap)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<P, V, C: Compare<P> + Default> KeyedFibonacciHeap<P, V, C> {
+    /// Restores a heap and maps each handle recorded in the snapshot to its new handle
+    ///
+    /// See [`FibonacciHeap::deserialize_with_handles`].
+    pub fn deserialize_with_handles<'de, D>(
+        deserializer: D,
+    ) -> Result<(Self, HashMap<crate::SavedHandle, NodeHandle>), D::Error>
+    where
+        P: serde::Deserialize<'de>,
+        V: serde::Deserialize<'de>,
+        D: serde::Deserializer<'de>,
+    {
+        let (heap, handles) = FibonacciHeap::deserialize_with_handles(deserializer)?;
+        Ok((KeyedFibonacciHeap { heap }, handles))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A payload that can be neither cloned nor compared
+    #[derive(Debug, PartialEq)]
+    struct Job {
+        name: &'static str,
+    }
+
+    #[test]
+    fn test_values_are_moved_not_cloned() {
+        let mut heap = KeyedFibonacciHeap::new();
+        heap.insert(3, Job { name: "c" }).unwrap();
+        let b = heap.insert(2, Job { name: "b" }).unwrap();
+        heap.insert(1, Job { name: "a" }).unwrap();
+
+        assert_eq!(heap.peek_min_ref().unwrap().1.name, "a");
+        assert_eq!(heap.extract_min(), Some((1, Job { name: "a" })));
+        heap.increase_key(&b, 5).unwrap();
+        assert_eq!(heap.extract_min(), Some((3, Job { name: "c" })));
+        assert_eq!(heap.delete(&b), Ok((5, Job { name: "b" })));
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_priority_updates() {
+        let mut heap = KeyedFibonacciHeap::new();
+        let handles: Vec<_> = (0..10).map(|i| heap.insert(i, i * 100).unwrap()).collect();
+        assert_eq!(heap.extract_min(), Some((0, 0)));
+
+        heap.decrease_key(&handles[9], -1).unwrap();
+        heap.update_key(&handles[1], 20).unwrap();
+        assert_eq!(
+            heap.decrease_key(&handles[2], 4),
+            Err(HeapError::InvalidKey)
+        );
+
+        assert_eq!(heap.extract_min(), Some((-1, 900)));
+        assert_eq!(heap.extract_min(), Some((2, 200)));
+
+        let rest: Vec<_> = std::iter::from_fn(|| heap.extract_min()).collect();
+        assert_eq!(rest.last(), Some(&(20, 100)));
+        assert_eq!(rest.len(), 7);
+    }
+
+    #[test]
+    fn test_max_priority_merge() {
+        let mut first = KeyedFibonacciHeap::new_max();
+        first.insert(1, "one").unwrap();
+        let mut second = KeyedFibonacciHeap::new_max();
+        second.insert(7, "seven").unwrap();
+
+        first.merge(second);
+        assert_eq!(first.peek_min(), Some((7, "seven")));
+        assert_eq!(first.len(), 2);
+    }
+}
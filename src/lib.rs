@@ -5,11 +5,18 @@
 //! making it ideal for algorithms like Dijkstra's and Prim's.
 //!
 //! # Features
-//! - O(1) amortized time for insert and merge operations
+//! - O(1) amortized time for insert operations, and merges that do not depend on the number of elements
 //! - O(1) amortized time for decrease key operations
 //! - O(log n) amortized time for extract minimum operations
 //! - Comprehensive error handling
-//! - Works with any type implementing `Ord + Clone`
+//! - A thread-safe `ConcurrentFibonacciHeap` with the `concurrent` feature (enabled by default)
+//! - Snapshots through `serde` with the `serde` feature, with a mapping from
+//!   saved handles to restored ones
+//! - Works with any type implementing `Ord`, or with a custom [`Compare`] order;
+//!   keys are moved out on extraction and never need to be `Clone`
+//! - [`PriorityQueue`] and [`AddressablePriorityQueue`] traits for swapping in other heaps,
+//!   with implementations for `std::collections::BinaryHeap`
+//! - Ready-made graph algorithms in [`algorithms`], such as Dijkstra's shortest paths, A* search and Prim's minimum spanning forest
 //!
 //! # Example
 //! ```
@@ -24,11 +31,47 @@
 //! assert_eq!(heap.extract_min(), Some(3));
 //! ```
 
-use std::cell::RefCell;
-use std::cmp;
-use std::collections::HashMap;
-use std::rc::{Rc, Weak};
-use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+pub mod algorithms;
+mod compare;
+#[cfg(feature = "concurrent")]
+mod concurrent;
+mod dot;
+mod indexed;
+mod iter;
+mod keyed;
+mod queue;
+#[cfg(feature = "serde")]
+mod snapshot;
+mod stats;
+#[cfg(test)]
+mod testing;
+mod validate;
+
+pub use compare::{Compare, FnComparator, KeyComparator, MaxOrder, MinOrder, Stable};
+#[cfg(feature = "concurrent")]
+pub use concurrent::ConcurrentFibonacciHeap;
+pub use indexed::{IndexedFibonacciHeap, PushOutcome};
+pub use iter::{Drain, DrainSorted, IntoIter, Iter};
+pub use keyed::KeyedFibonacciHeap;
+pub use queue::{AddressablePriorityQueue, MinBinaryHeap, PriorityQueue};
+#[cfg(feature = "serde")]
+pub use snapshot::SavedHandle;
+#[cfg(feature = "stats")]
+pub use stats::HeapStats;
+pub use validate::InvariantViolation;
+
+use std::cmp::{self, Ordering};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering as AtomicOrdering};
+
+/// Panic message for arena lookups that must hit an occupied slot
+const LIVE_NODE: &str = "node index refers to a live node";
+
+/// Source of unique heap identifiers, used to tell handles of different heaps apart
+static NEXT_HEAP_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Source of insertion sequence numbers, shared so that merged heaps agree on them
+static NEXT_SEQUENCE: AtomicU64 = AtomicU64::new(0);
 
 /// Error types for Fibonacci Heap operations
 #[derive(Debug, PartialEq)]
@@ -38,48 +81,167 @@ pub enum HeapError {
     HeapEmpty,
 }
 
+/// An opaque handle to a node in a [`FibonacciHeap`]
+///
+/// Handles are returned by [`FibonacciHeap::insert`] and identify the node in
+/// later calls such as [`FibonacciHeap::decrease_key`]. They do not grant
+/// access to the key, so the heap invariant cannot be broken from outside.
+///
+/// A handle records the identity of the heap that created the node and the
+/// generation of the slot it occupies. Using it after the node was removed,
+/// after [`FibonacciHeap::clear`], or on an unrelated heap yields
+/// `HeapError::NodeNotFound` rather than touching another node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    heap: usize,
+    slot: usize,
+    generation: u32,
+}
+
+/// Position of a node: its arena segment and the slot within that segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct NodeRef {
+    segment: usize,
+    slot: usize,
+}
+
+/// A reference from one node to another, stored relative to the holder
+///
+/// Merging appends whole segments, which shifts their positions but keeps
+/// the distance between segments that arrive together, so relative links
+/// stay valid without visiting any node.
+///
+/// The compact fields limit a segment to `u32::MAX + 1` slots and a heap to
+/// about `i32::MAX` segments; going past either panics instead of wrapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Link {
+    segment_offset: i32,
+    slot: u32,
+}
+
+impl Link {
+    /// Creates a link stored in `from` that points at `to`
+    fn new(from: NodeRef, to: NodeRef) -> Self {
+        Link {
+            segment_offset: i32::try_from(to.segment as isize - from.segment as isize)
+                .expect("too many segments for a relative link"),
+            slot: u32::try_from(to.slot).expect("too many slots for a link"),
+        }
+    }
+
+    /// Resolves a link stored in `from`
+    fn target(self, from: NodeRef) -> NodeRef {
+        NodeRef {
+            segment: from
+                .segment
+                .wrapping_add_signed(self.segment_offset as isize),
+            slot: self.slot as usize,
+        }
+    }
+}
+
 /// A node in the Fibonacci Heap
-#[derive(Debug)]
-pub struct Node<T> {
-    pub key: T,
+#[derive(Debug, Clone)]
+struct Node<T> {
+    key: T,
+    sequence: u64, // Insertion order, only recorded for stable comparators
     degree: usize,
     marked: bool,
-    parent: Option<Weak<RefCell<Node<T>>>>,
-    children: Vec<Rc<RefCell<Node<T>>>>,
-    id: usize, // Unique identifier for node validation
+    parent: Option<Link>,
+    child: Option<Link>, // Any one child; the others are its siblings
+    left: Link,          // Previous sibling, or previous root for roots
+    right: Link,         // Next sibling, or next root for roots
 }
 
 impl<T> Node<T> {
-    /// Creates a new node with the given key and unique ID
-    fn new(key: T, id: usize) -> Rc<RefCell<Self>> {
-        Rc::new(RefCell::new(Node {
+    /// Creates a new detached node that forms a root list of its own
+    fn new(key: T, slot: usize, sequence: u64) -> Self {
+        let itself = Link {
+            segment_offset: 0,
+            slot: u32::try_from(slot).expect("too many slots for a link"),
+        };
+        Node {
             key,
+            sequence,
             degree: 0,
             marked: false,
             parent: None,
-            children: Vec::new(),
-            id,
-        }))
+            child: None,
+            left: itself,
+            right: itself,
+        }
+    }
+}
+
+/// An arena slot holding a node, or nothing once the node has been removed
+///
+/// The generation is bumped every time the slot is vacated, so handles to an
+/// earlier occupant stop matching once the slot is reused.
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    generation: u32,
+    node: Option<Node<T>>,
+}
+
+/// A block of node storage created by a single heap
+///
+/// New nodes are always allocated in a heap's own segment; segments adopted
+/// through [`FibonacciHeap::merge`] only ever shrink. An adopted segment that
+/// runs empty is dropped; unless it was the last one, its position stays
+/// vacant until a later merge reuses it or the segments after it are gone.
+#[derive(Debug)]
+struct Segment<T> {
+    heap: usize, // Id of the heap that created this segment
+    slots: Vec<Slot<T>>,
+    live: usize,
+}
+
+impl<T> Segment<T> {
+    fn new() -> Self {
+        Segment {
+            heap: NEXT_HEAP_ID.fetch_add(1, AtomicOrdering::Relaxed),
+            slots: Vec::new(),
+            live: 0,
+        }
+    }
+}
+
+impl<T: Clone> Segment<T> {
+    /// Copies the segment under a fresh identity, so handles to the original
+    /// do not resolve in the copy
+    fn fork(&self) -> Self {
+        Segment {
+            slots: self.slots.clone(),
+            live: self.live,
+            ..Segment::new()
+        }
     }
 }
 
 /// A Fibonacci Heap data structure
+///
+/// Elements are ordered by the comparator `C`, which defaults to [`MinOrder`].
+/// "Minimum" and "decrease" always refer to that order, so with [`MaxOrder`]
+/// `extract_min` returns the largest element and `decrease_key` raises a key.
 #[derive(Debug)]
-pub struct FibonacciHeap<T> {
-    min: Option<Rc<RefCell<Node<T>>>>,
-    root_list: Vec<Rc<RefCell<Node<T>>>>,
+pub struct FibonacciHeap<T, C = MinOrder> {
+    min: Option<NodeRef>, // Also the entry point into the circular root list
     node_count: usize,
-    next_id: AtomicUsize,
-    active_nodes: HashMap<usize, Weak<RefCell<Node<T>>>>,
+    segments: Vec<Segment<T>>, // segments[0] is this heap's own segment
+    segment_index: HashMap<usize, usize>, // Segment positions by creating heap id
+    free: Vec<usize>,          // Vacant slots of the own segment
+    vacant: BTreeSet<usize>,   // Positions of adopted segments that ran empty
+    comparator: C,
+    stats: stats::Collector,
 }
 
-impl<T: Ord + Clone> Default for FibonacciHeap<T> {
+impl<T, C: Compare<T> + Default> Default for FibonacciHeap<T, C> {
     fn default() -> Self {
-        Self::new()
+        Self::with_comparator(C::default())
     }
 }
 
-impl<T: Ord + Clone> FibonacciHeap<T> {
+impl<T: Ord> FibonacciHeap<T> {
     /// Creates a new empty Fibonacci Heap
     ///
     /// # Examples
@@ -89,22 +251,112 @@ impl<T: Ord + Clone> FibonacciHeap<T> {
     /// assert!(heap.is_empty());
     /// ```
     pub fn new() -> Self {
+        Self::with_comparator(MinOrder)
+    }
+}
+
+impl<T: Ord> FibonacciHeap<T, MaxOrder> {
+    /// Creates a new empty max-heap, which extracts the largest element first
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new_max();
+    /// heap.insert(5).unwrap();
+    /// heap.insert(10).unwrap();
+    /// assert_eq!(heap.extract_min(), Some(10));
+    /// ```
+    pub fn new_max() -> Self {
+        Self::with_comparator(MaxOrder)
+    }
+}
+
+impl<T: Ord> FibonacciHeap<T, Stable> {
+    /// Creates a new empty min-heap that extracts equal elements in insertion order
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new_stable();
+    /// heap.insert((1, "first")).unwrap();
+    /// heap.insert((0, "urgent")).unwrap();
+    /// heap.insert((1, "second")).unwrap();
+    ///
+    /// let order: Vec<_> = heap.into_sorted_vec();
+    /// assert_eq!(order, vec![(0, "urgent"), (1, "first"), (1, "second")]);
+    /// ```
+    pub fn new_stable() -> Self {
+        Self::with_comparator(Stable(MinOrder))
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering> FibonacciHeap<T, FnComparator<F>> {
+    /// Creates a new empty heap ordered by a comparison closure
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new_by(|a: &i32, b: &i32| b.cmp(a));
+    /// heap.insert(5).unwrap();
+    /// heap.insert(10).unwrap();
+    /// assert_eq!(heap.extract_min(), Some(10));
+    /// ```
+    pub fn new_by(compare: F) -> Self {
+        Self::with_comparator(FnComparator(compare))
+    }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> FibonacciHeap<T, KeyComparator<F>> {
+    /// Creates a new empty heap ordered by a key extracted from each element
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new_by_key(|pair: &(i32, &str)| pair.0);
+    /// heap.insert((2, "two")).unwrap();
+    /// heap.insert((1, "one")).unwrap();
+    /// assert_eq!(heap.extract_min(), Some((1, "one")));
+    /// ```
+    pub fn new_by_key(key: F) -> Self {
+        Self::with_comparator(KeyComparator(key))
+    }
+}
+
+impl<T, C: Compare<T>> FibonacciHeap<T, C> {
+    /// Creates a new empty heap ordered by the given comparator
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::{FibonacciHeap, MaxOrder};
+    ///
+    /// let heap = FibonacciHeap::<i32, _>::with_comparator(MaxOrder);
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn with_comparator(comparator: C) -> Self {
+        let segment = Segment::new();
         FibonacciHeap {
             min: None,
-            root_list: Vec::new(),
             node_count: 0,
-            next_id: AtomicUsize::new(0),
-            active_nodes: HashMap::new(),
+            segment_index: HashMap::from([(segment.heap, 0)]),
+            segments: vec![segment],
+            free: Vec::new(),
+            vacant: BTreeSet::new(),
+            comparator,
+            stats: Default::default(),
         }
     }
 
-    /// Inserts a new key into the heap and returns a reference to the created node
+    /// Inserts a new key into the heap and returns a handle to the created node
     ///
     /// # Arguments
     /// * `key` - The value to insert
     ///
     /// # Returns
-    /// `Result` containing a node reference or an error
+    /// `Result` containing a node handle or an error
     ///
     /// # Examples
     /// ```
@@ -112,30 +364,90 @@ impl<T: Ord + Clone> FibonacciHeap<T> {
     /// let mut heap = FibonacciHeap::new();
     /// let node = heap.insert(42).unwrap();
     /// ```
-    pub fn insert(&mut self, key: T) -> Result<Rc<RefCell<Node<T>>>, HeapError> {
-        let id = self.next_id.fetch_add(1, AtomicOrdering::SeqCst);
-        let node = Node::new(key, id);
-
-        // Store weak reference for validation
-        self.active_nodes.insert(id, Rc::downgrade(&node));
-
-        self.root_list.push(Rc::clone(&node));
+    pub fn insert(&mut self, key: T) -> Result<NodeHandle, HeapError> {
+        let node = self.allocate(key);
         self.node_count += 1;
 
-        // Update minimum if needed
-        match &self.min {
-            Some(min) if node.borrow().key < min.borrow().key => {
-                self.min = Some(Rc::clone(&node));
+        // Add to the root list and update minimum if needed
+        match self.min {
+            Some(min) => {
+                self.ring_splice(min, node);
+                if self.less(node, min) {
+                    self.min = Some(node);
+                }
             }
-            None => self.min = Some(Rc::clone(&node)),
-            _ => (),
+            None => self.min = Some(node),
         }
 
-        Ok(node)
+        Ok(self.handle(node))
+    }
+
+    /// Inserts every key of an iterator and returns their handles in order
+    ///
+    /// Storage for the new nodes is reserved up front from the iterator's size
+    /// hint, and they join the root list in insertion order, which is cheaper
+    /// than calling [`insert`](Self::insert) for each key.
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new_by_key(|word: &&str| word.len());
+    /// let handles = heap.extend_with_handles(["ccc", "a", "bb"]);
+    ///
+    /// heap.decrease_key(&handles[0], "").unwrap();
+    /// assert_eq!(heap.extract_min(), Some(""));
+    /// ```
+    pub fn extend_with_handles<I: IntoIterator<Item = T>>(&mut self, keys: I) -> Vec<NodeHandle> {
+        let keys = keys.into_iter();
+        let mut handles = Vec::with_capacity(keys.size_hint().0);
+        self.bulk_insert(keys, |handle| handles.push(handle));
+        handles
+    }
+
+    /// Adds keys as new roots, reporting each node's handle to `inserted`
+    fn bulk_insert<I: Iterator<Item = T>>(
+        &mut self,
+        keys: I,
+        mut inserted: impl FnMut(NodeHandle),
+    ) {
+        let additional = keys.size_hint().0.saturating_sub(self.free.len());
+        self.segments[0].slots.reserve(additional);
+
+        // Link and count every node as soon as it exists, so the heap stays
+        // whole if the iterator panics; each one follows the previous one to
+        // keep insertion order in the root list
+        let mut last = self.min;
+        for key in keys {
+            let node = self.allocate(key);
+            self.node_count += 1;
+            if let Some(last) = last {
+                self.ring_splice(last, node);
+            }
+            match self.min {
+                Some(min) if !self.less(node, min) => {}
+                _ => self.min = Some(node),
+            }
+            last = Some(node);
+            inserted(self.handle(node));
+        }
     }
 
     /// Merges another Fibonacci Heap into this one
     ///
+    /// The merged heap keeps this heap's comparator; `other` is expected to
+    /// use the same ordering. Handles issued by `other` remain valid.
+    ///
+    /// The other heap's storage and root list are spliced in as they are, so
+    /// the cost does not depend on the number of elements in either heap.
+    /// Storage comes in segments: every heap has one of its own and adopts
+    /// those of each heap merged into it. Merging takes time linear in the
+    /// number of segments `other` holds, which is constant unless `other`
+    /// has absorbed heaps itself. An adopted segment is dropped once its last
+    /// element is removed, except that one emptied between two segments that
+    /// still hold elements stays behind as a gap until the later ones are
+    /// gone, since links between segments depend on their spacing.
+    ///
     /// # Arguments
     /// * `other` - The heap to merge into this one
     ///
@@ -152,23 +464,64 @@ impl<T: Ord + Clone> FibonacciHeap<T> {
     /// heap1.merge(heap2);
     /// assert_eq!(heap1.extract_min(), Some(5));
     /// ```
-    pub fn merge(&mut self, other: FibonacciHeap<T>) {
-        // Merge root lists
-        self.root_list.extend(other.root_list);
+    pub fn merge(&mut self, other: FibonacciHeap<T, C>) {
+        self.stats.absorb(&other.stats);
+        let Some(other_min) = other.min else {
+            return;
+        };
         self.node_count += other.node_count;
 
-        // Merge active nodes
-        self.active_nodes.extend(other.active_nodes);
+        // Adopt the segments that still hold nodes. Links between them are
+        // relative, so they must keep their spacing; a lone segment has no
+        // such constraint and can fill a vacant position.
+        let mut segments = other.segments;
+        let first = segments.iter().position(|segment| segment.live > 0);
+        let last = segments.iter().rposition(|segment| segment.live > 0);
+        let (Some(first), Some(last)) = (first, last) else {
+            unreachable!("a heap with a minimum holds nodes");
+        };
+        let shift = if first == last {
+            let segment = segments.swap_remove(first);
+            let heap = segment.heap;
+            let position = match self.vacant.pop_first() {
+                Some(position) => {
+                    self.segments[position] = segment;
+                    position
+                }
+                None => {
+                    self.segments.push(segment);
+                    self.segments.len() - 1
+                }
+            };
+            self.segment_index.insert(heap, position);
+            position as isize - first as isize
+        } else {
+            let offset = self.segments.len();
+            for (position, mut segment) in segments.drain(first..=last).enumerate() {
+                if segment.live > 0 {
+                    self.segment_index.insert(segment.heap, offset + position);
+                } else {
+                    segment.slots = Vec::new();
+                    self.vacant.insert(offset + position);
+                }
+                self.segments.push(segment);
+            }
+            offset as isize - first as isize
+        };
 
-        // Update minimum if needed
-        if let Some(other_min) = other.min {
-            match &self.min {
-                Some(self_min) if other_min.borrow().key < self_min.borrow().key => {
+        // Merge root lists and update minimum if needed
+        let other_min = NodeRef {
+            segment: other_min.segment.wrapping_add_signed(shift),
+            slot: other_min.slot,
+        };
+        match self.min {
+            Some(self_min) => {
+                self.ring_splice(self_min, other_min);
+                if self.less(other_min, self_min) {
                     self.min = Some(other_min);
                 }
-                None => self.min = Some(other_min),
-                _ => (),
             }
+            None => self.min = Some(other_min),
         }
     }
 
@@ -188,30 +541,89 @@ impl<T: Ord + Clone> FibonacciHeap<T> {
     /// assert_eq!(heap.extract_min(), Some(5));
     /// ```
     pub fn extract_min(&mut self) -> Option<T> {
-        let min_node = self.min.take()?;
-        let min_key = min_node.borrow().key.clone();
-        let min_id = min_node.borrow().id;
+        let min = self.min?;
+        Some(self.remove_root(min))
+    }
+
+    /// Deletes an arbitrary node from the heap and returns its key
+    ///
+    /// # Arguments
+    /// * `node` - Handle of the node to remove
+    ///
+    /// # Returns
+    /// `Result` containing the removed key, or `HeapError::NodeNotFound`
+    /// if the handle no longer refers to a node in this heap
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// heap.insert(10).unwrap();
+    /// let node = heap.insert(5).unwrap();
+    ///
+    /// assert_eq!(heap.delete(&node), Ok(5));
+    /// assert_eq!(heap.extract_min(), Some(10));
+    /// ```
+    pub fn delete(&mut self, node: &NodeHandle) -> Result<T, HeapError> {
+        let node = self.resolve(node)?;
+
+        // Move the node to the root list so it can be removed like a minimum
+        if let Some(parent) = self.parent(node) {
+            self.cut(node, parent);
+            self.cascading_cut(parent);
+        }
 
-        // Remove from active nodes
-        self.active_nodes.remove(&min_id);
+        Ok(self.remove_root(node))
+    }
+
+    /// Removes a root node, promoting its children to the root list
+    fn remove_root(&mut self, node: NodeRef) -> T {
+        self.promote_children(node);
+
+        // Remove node from root list
+        let next = self.right(node);
+        self.ring_remove(node);
+        let removed = self.release(node);
+        self.node_count -= 1;
 
-        // Add children to root list
-        let children = std::mem::take(&mut min_node.borrow_mut().children);
-        for child in children {
-            child.borrow_mut().parent = None;
-            self.root_list.push(child);
+        if self.min == Some(node) {
+            if next == node {
+                self.min = None;
+            } else {
+                self.min = Some(next);
+                self.consolidate();
+            }
         }
 
-        // Remove min node from root list
-        self.root_list.retain(|node| !Rc::ptr_eq(node, &min_node));
-        if self.root_list.is_empty() {
-            self.min = None;
-        } else {
-            self.consolidate();
+        removed.key
+    }
+
+    /// Moves all children of a node to the root list
+    fn promote_children(&mut self, node: NodeRef) {
+        let Some(child) = self.node_mut(node).child.take() else {
+            return;
+        };
+        self.node_mut(node).degree = 0;
+
+        // Roots are never marked, so marks only count losses below a parent
+        let first = child.target(node);
+        let mut current = first;
+        loop {
+            let child = self.node_mut(current);
+            child.parent = None;
+            child.marked = false;
+            current = self.right(current);
+            if current == first {
+                break;
+            }
         }
-        self.node_count -= 1;
 
-        Some(min_key)
+        // The children already form a circular list, so splice it in whole
+        match self.min {
+            Some(min) => self.ring_splice(min, first),
+            None => self.min = Some(first),
+        }
     }
 
     /// Consolidates the trees in the heap to maintain the Fibonacci Heap properties
@@ -220,24 +632,27 @@ impl<T: Ord + Clone> FibonacciHeap<T> {
         let phi = 1.61803398874989_f64;
         let theoretical_max_degree = ((self.node_count as f64).log2() * 2.0 * phi) as usize + 2;
         let max_degree = cmp::max(theoretical_max_degree, 32);
-        let mut degree_table: Vec<Option<Rc<RefCell<Node<T>>>>> = vec![None; max_degree];
-        let mut new_min = None;
+        let mut degree_table: Vec<Option<NodeRef>> = vec![None; max_degree];
 
         // Process all root nodes
-        let roots = std::mem::take(&mut self.root_list);
+        let roots = match self.min.take() {
+            Some(start) => self.ring_members(start),
+            None => return,
+        };
+        self.stats.consolidation(roots.len());
         for root in roots {
             let mut current = root;
-            let mut degree = current.borrow().degree;
+            let mut degree = self.node(current).degree;
 
             // Combine trees with same degree
             while let Some(existing) = degree_table[degree].take() {
-                if current.borrow().key < existing.borrow().key {
-                    self.link(existing, &current);
+                if self.less(current, existing) {
+                    self.link(existing, current);
                 } else {
-                    self.link(current, &existing);
+                    self.link(current, existing);
                     current = existing;
                 }
-                degree = current.borrow().degree;
+                degree = self.node(current).degree;
 
                 // Extend degree table if needed
                 if degree >= degree_table.len() {
@@ -245,40 +660,51 @@ impl<T: Ord + Clone> FibonacciHeap<T> {
                 }
             }
 
-            degree_table[degree] = Some(current.clone());
+            degree_table[degree] = Some(current);
+        }
 
-            // Track new minimum
-            if new_min
-                .as_ref()
-                .is_none_or(|min: &Rc<RefCell<Node<T>>>| current.borrow().key < min.borrow().key)
-            {
-                new_min = Some(current);
+        // Rebuild root list from degree table, tracking the new minimum
+        for root in degree_table.into_iter().flatten() {
+            self.node_mut(root).left = Link::new(root, root);
+            self.node_mut(root).right = Link::new(root, root);
+            match self.min {
+                Some(min) => {
+                    self.ring_splice(min, root);
+                    if self.less(root, min) {
+                        self.min = Some(root);
+                    }
+                }
+                None => self.min = Some(root),
             }
         }
-
-        // Rebuild root list from degree table
-        self.root_list = degree_table.into_iter().flatten().collect();
-        self.min = new_min;
     }
 
     /// Links two trees by making one a child of the other
-    fn link(&mut self, child: Rc<RefCell<Node<T>>>, parent: &Rc<RefCell<Node<T>>>) {
-        // Remove child from root list
-        self.root_list.retain(|node| !Rc::ptr_eq(node, &child));
-
+    ///
+    /// Only used while consolidating, when the root list is rebuilt afterwards.
+    fn link(&mut self, child: NodeRef, parent: NodeRef) {
         // Update child's parent
-        child.borrow_mut().parent = Some(Rc::downgrade(parent));
-        child.borrow_mut().marked = false;
+        let child_node = self.node_mut(child);
+        child_node.parent = Some(Link::new(child, parent));
+        child_node.marked = false;
+        self.set_left(child, child);
+        self.set_right(child, child);
 
         // Add child to parent's children
-        parent.borrow_mut().children.push(child);
-        parent.borrow_mut().degree += 1;
+        match self.first_child(parent) {
+            Some(first) => self.ring_splice(first, child),
+            None => self.node_mut(parent).child = Some(Link::new(parent, child)),
+        }
+        self.node_mut(parent).degree += 1;
+        self.stats.link(self.node(parent).degree);
     }
 
     /// Decreases the key of a node
     ///
+    /// The new key must not come after the current one in the heap order.
+    ///
     /// # Arguments
-    /// * `node` - Reference to the node to update
+    /// * `node` - Handle of the node to update
     /// * `new_key` - The new key value
     ///
     /// # Returns
@@ -296,111 +722,372 @@ impl<T: Ord + Clone> FibonacciHeap<T> {
     /// heap.decrease_key(&node, 5).unwrap();
     /// assert_eq!(heap.extract_min(), Some(5));
     /// ```
-    pub fn decrease_key(
-        &mut self,
-        node: &Rc<RefCell<Node<T>>>,
-        new_key: T,
-    ) -> Result<(), HeapError> {
-        // Validate node reference
-        let node_id = node.borrow().id;
-        if !self.active_nodes.contains_key(&node_id) {
-            return Err(HeapError::NodeNotFound);
+    pub fn decrease_key(&mut self, node: &NodeHandle, new_key: T) -> Result<(), HeapError> {
+        self.modify_key(node, |comparator, key| {
+            // Validate key
+            if comparator.compare(&new_key, key) == Ordering::Greater {
+                return Err(HeapError::InvalidKey);
+            }
+            *key = new_key;
+            Ok(Ordering::Less)
+        })
+    }
+
+    /// Increases the key of a node
+    ///
+    /// The node is cut from its parent, its children are moved to the root
+    /// list and the node is kept as a root with the new key.
+    ///
+    /// # Arguments
+    /// * `node` - Handle of the node to update
+    /// * `new_key` - The new key value, which must not be smaller than the current one
+    ///
+    /// # Returns
+    /// `Result` indicating success or an error
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// let node = heap.insert(5).unwrap();
+    /// heap.insert(10).unwrap();
+    ///
+    /// heap.increase_key(&node, 20).unwrap();
+    /// assert_eq!(heap.extract_min(), Some(10));
+    /// assert_eq!(heap.extract_min(), Some(20));
+    /// ```
+    pub fn increase_key(&mut self, node: &NodeHandle, new_key: T) -> Result<(), HeapError> {
+        self.modify_key(node, |comparator, key| {
+            // Validate key
+            if comparator.compare(&new_key, key) == Ordering::Less {
+                return Err(HeapError::InvalidKey);
+            }
+            *key = new_key;
+            Ok(Ordering::Greater)
+        })
+    }
+
+    /// Changes the key of a node in either direction
+    ///
+    /// Behaves like [`decrease_key`](Self::decrease_key) or
+    /// [`increase_key`](Self::increase_key) depending on the new key.
+    ///
+    /// # Arguments
+    /// * `node` - Handle of the node to update
+    /// * `new_key` - The new key value
+    ///
+    /// # Returns
+    /// `Result` indicating success or an error
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// let a = heap.insert(10).unwrap();
+    /// let b = heap.insert(20).unwrap();
+    ///
+    /// heap.update_key(&a, 30).unwrap();
+    /// heap.update_key(&b, 5).unwrap();
+    /// assert_eq!(heap.extract_min(), Some(5));
+    /// assert_eq!(heap.extract_min(), Some(30));
+    /// ```
+    pub fn update_key(&mut self, node: &NodeHandle, new_key: T) -> Result<(), HeapError> {
+        self.modify_key(node, |comparator, key| {
+            let direction = comparator.compare(&new_key, key);
+            *key = new_key;
+            Ok(direction)
+        })
+    }
+
+    /// Rewrites a node's key in place and restores the heap order
+    ///
+    /// `update` receives the comparator and the current key, mutates the key
+    /// and reports whether it moved toward the front (`Less`) or the back
+    /// (`Greater`) of the heap order. Errors returned by `update` are passed
+    /// through before anything is restructured.
+    pub(crate) fn modify_key<F>(&mut self, node: &NodeHandle, update: F) -> Result<(), HeapError>
+    where
+        F: FnOnce(&C, &mut T) -> Result<Ordering, HeapError>,
+    {
+        let node = self.resolve(node)?;
+        let key = &mut self.segments[node.segment].slots[node.slot]
+            .node
+            .as_mut()
+            .expect(LIVE_NODE)
+            .key;
+        let direction = update(&self.comparator, key)?;
+
+        match direction {
+            Ordering::Less | Ordering::Equal => self.restore_after_decrease(node),
+            Ordering::Greater => self.restore_after_increase(node),
         }
 
-        // Validate key
-        if new_key > node.borrow().key {
-            return Err(HeapError::InvalidKey);
+        Ok(())
+    }
+
+    /// Restores the heap order after a node's key moved toward the front
+    fn restore_after_decrease(&mut self, node: NodeRef) {
+        if let Some(parent) = self.parent(node) {
+            if self.less(node, parent) {
+                self.cut(node, parent);
+                self.cascading_cut(parent);
+            }
         }
 
-        // Update key
-        node.borrow_mut().key = new_key.clone();
+        // Update minimum if needed
+        match self.min {
+            Some(min) if self.less(node, min) => {
+                self.min = Some(node);
+            }
+            None => self.min = Some(node),
+            _ => (),
+        }
+    }
 
-        // Check if heap property is violated - extract parent info first
-        let parent = {
-            let node_ref = node.borrow();
-            if let Some(parent_weak) = &node_ref.parent {
-                parent_weak.upgrade()
-            } else {
-                None
+    /// Restores the heap order after a node's key moved toward the back
+    ///
+    /// The node is cut from its parent and its children are moved to the root
+    /// list, leaving it as a root with the new key.
+    fn restore_after_increase(&mut self, node: NodeRef) {
+        // Detach the node from both its parent and its children
+        if let Some(parent) = self.parent(node) {
+            self.cut(node, parent);
+            self.cascading_cut(parent);
+        }
+        self.promote_children(node);
+
+        // The old minimum may no longer be the smallest root
+        if self.min == Some(node) {
+            self.consolidate();
+        }
+    }
+
+    /// Returns `true` if node `a` comes strictly before node `b` in the heap order
+    fn less(&self, a: NodeRef, b: NodeRef) -> bool {
+        self.stats.comparison();
+        self.precedes(a, b)
+    }
+
+    /// Compares two nodes like [`less`](Self::less), without counting it
+    fn precedes(&self, a: NodeRef, b: NodeRef) -> bool {
+        let (a, b) = (self.node(a), self.node(b));
+        match self.comparator.compare(&a.key, &b.key) {
+            Ordering::Less => true,
+            Ordering::Equal => C::STABLE && a.sequence < b.sequence,
+            Ordering::Greater => false,
+        }
+    }
+
+    /// Stores a new detached node in this heap's own segment
+    fn allocate(&mut self, key: T) -> NodeRef {
+        let own = &mut self.segments[0];
+        let slot = self.free.pop().unwrap_or_else(|| {
+            own.slots.push(Slot {
+                generation: 0,
+                node: None,
+            });
+            own.slots.len() - 1
+        });
+        let sequence = if C::STABLE {
+            NEXT_SEQUENCE.fetch_add(1, AtomicOrdering::Relaxed)
+        } else {
+            0
+        };
+        own.slots[slot].node = Some(Node::new(key, slot, sequence));
+        own.live += 1;
+        NodeRef { segment: 0, slot }
+    }
+
+    /// Takes a node out of its slot, invalidating all handles to it
+    fn release(&mut self, at: NodeRef) -> Node<T> {
+        let segment = &mut self.segments[at.segment];
+        let slot = &mut segment.slots[at.slot];
+        slot.generation = slot.generation.wrapping_add(1);
+        let node = slot.node.take().expect(LIVE_NODE);
+        segment.live -= 1;
+
+        if at.segment == 0 {
+            // A slot whose generation wrapped around is retired for good, so
+            // no generation is ever handed out twice for the same slot
+            if slot.generation != 0 {
+                self.free.push(at.slot);
             }
-        }; // node_ref goes out of scope here, releasing the borrow
+        } else if segment.live == 0 {
+            // Adopted segments never allocate again, so the whole segment can go
+            segment.slots = Vec::new();
+            let heap = segment.heap;
+            self.segment_index.remove(&heap);
+            self.vacant.insert(at.segment);
+            // Vacant positions at the end need not be kept for spacing
+            while self.vacant.last() == Some(&(self.segments.len() - 1)) {
+                self.vacant.pop_last();
+                self.segments.pop();
+            }
+        }
+        node
+    }
 
-        if let Some(parent) = parent {
-            if new_key < parent.borrow().key {
-                self.cut(node, &parent);
-                self.cascading_cut(&parent);
+    /// Creates a handle for the node at `at`
+    fn handle(&self, at: NodeRef) -> NodeHandle {
+        let segment = &self.segments[at.segment];
+        NodeHandle {
+            heap: segment.heap,
+            slot: at.slot,
+            generation: segment.slots[at.slot].generation,
+        }
+    }
+
+    /// Looks up the node behind a handle, failing if it is no longer in the heap
+    fn resolve(&self, handle: &NodeHandle) -> Result<NodeRef, HeapError> {
+        let segment = if self.segments[0].heap == handle.heap {
+            0
+        } else {
+            *self
+                .segment_index
+                .get(&handle.heap)
+                .ok_or(HeapError::NodeNotFound)?
+        };
+
+        match self.segments[segment].slots.get(handle.slot) {
+            Some(slot) if slot.generation == handle.generation && slot.node.is_some() => {
+                Ok(NodeRef {
+                    segment,
+                    slot: handle.slot,
+                })
             }
+            _ => Err(HeapError::NodeNotFound),
         }
+    }
 
-        // Update minimum if needed
-        if self.min.is_none() || new_key < self.min.as_ref().unwrap().borrow().key {
-            self.min = Some(Rc::clone(node));
+    fn node(&self, at: NodeRef) -> &Node<T> {
+        self.segments[at.segment].slots[at.slot]
+            .node
+            .as_ref()
+            .expect(LIVE_NODE)
+    }
+
+    fn node_mut(&mut self, at: NodeRef) -> &mut Node<T> {
+        self.segments[at.segment].slots[at.slot]
+            .node
+            .as_mut()
+            .expect(LIVE_NODE)
+    }
+
+    fn parent(&self, node: NodeRef) -> Option<NodeRef> {
+        self.node(node).parent.map(|parent| parent.target(node))
+    }
+
+    fn first_child(&self, node: NodeRef) -> Option<NodeRef> {
+        self.node(node).child.map(|child| child.target(node))
+    }
+
+    fn left(&self, node: NodeRef) -> NodeRef {
+        self.node(node).left.target(node)
+    }
+
+    fn right(&self, node: NodeRef) -> NodeRef {
+        self.node(node).right.target(node)
+    }
+
+    fn set_left(&mut self, node: NodeRef, left: NodeRef) {
+        self.node_mut(node).left = Link::new(node, left);
+    }
+
+    fn set_right(&mut self, node: NodeRef, right: NodeRef) {
+        self.node_mut(node).right = Link::new(node, right);
+    }
+
+    /// Joins the circular list containing `b` into the one containing `a`
+    fn ring_splice(&mut self, a: NodeRef, b: NodeRef) {
+        let a_right = self.right(a);
+        let b_left = self.left(b);
+        self.set_right(a, b);
+        self.set_left(b, a);
+        self.set_right(b_left, a_right);
+        self.set_left(a_right, b_left);
+    }
+
+    /// Unlinks a node from its circular list, leaving it in a list of its own
+    fn ring_remove(&mut self, node: NodeRef) {
+        let left = self.left(node);
+        let right = self.right(node);
+        self.set_right(left, right);
+        self.set_left(right, left);
+        self.set_left(node, node);
+        self.set_right(node, node);
+    }
+
+    /// Collects the members of a circular list, starting at `start`
+    fn ring_members(&self, start: NodeRef) -> Vec<NodeRef> {
+        let mut members = vec![start];
+        let mut current = self.right(start);
+        while current != start {
+            members.push(current);
+            current = self.right(current);
         }
+        members
+    }
 
-        Ok(())
+    /// Adds a detached node to the root list without touching the minimum
+    fn add_root(&mut self, node: NodeRef) {
+        self.set_left(node, node);
+        self.set_right(node, node);
+        match self.min {
+            Some(min) => self.ring_splice(min, node),
+            None => self.min = Some(node),
+        }
     }
 
     /// Cuts a node from its parent and moves it to the root list
-    fn cut(&mut self, node: &Rc<RefCell<Node<T>>>, parent: &Rc<RefCell<Node<T>>>) {
+    fn cut(&mut self, node: NodeRef, parent: NodeRef) {
         // Remove node from parent's children
-        parent
-            .borrow_mut()
-            .children
-            .retain(|child| !Rc::ptr_eq(child, node));
-        parent.borrow_mut().degree -= 1;
+        if self.first_child(parent) == Some(node) {
+            let sibling = self.right(node);
+            self.node_mut(parent).child = (sibling != node).then(|| Link::new(parent, sibling));
+        }
+        self.ring_remove(node);
+        self.node_mut(parent).degree -= 1;
+        self.stats.cut();
 
         // Add node to root list
-        node.borrow_mut().parent = None;
-        node.borrow_mut().marked = false;
-        self.root_list.push(Rc::clone(node));
+        let node_ref = self.node_mut(node);
+        node_ref.parent = None;
+        node_ref.marked = false;
+        self.add_root(node);
     }
 
     /// Performs cascading cuts on a node's ancestors if needed
-    fn cascading_cut(&mut self, node: &Rc<RefCell<Node<T>>>) {
-        // Extract parent info first
-        let (parent, is_marked) = {
-            let node_ref = node.borrow();
-            let parent = if let Some(parent_weak) = &node_ref.parent {
-                parent_weak.upgrade()
-            } else {
-                None
-            };
-            let is_marked = node_ref.marked;
-            (parent, is_marked)
-        }; // node_ref goes out of scope here, releasing the borrow
-
-        if let Some(parent) = parent {
-            if !is_marked {
-                node.borrow_mut().marked = true;
+    fn cascading_cut(&mut self, node: NodeRef) {
+        if let Some(parent) = self.parent(node) {
+            if !self.node(node).marked {
+                self.node_mut(node).marked = true;
             } else {
-                self.cut(node, &parent);
-                self.cascading_cut(&parent);
+                self.stats.cascading_cut();
+                self.cut(node, parent);
+                self.cascading_cut(parent);
             }
         }
     }
 
-    /// Returns the minimum value without removing it
+    /// Borrows the minimum value without removing or cloning it
     ///
     /// # Returns
-    /// The minimum value or `None` if the heap is empty
+    /// A reference to the minimum value or `None` if the heap is empty
     ///
     /// # Examples
     /// ```
     /// use fibonacci_heap::FibonacciHeap;
     ///
     /// let mut heap = FibonacciHeap::new();
-    /// heap.insert(10).unwrap();
-    /// heap.insert(5).unwrap();
+    /// heap.insert(vec![3, 4]).unwrap();
+    /// heap.insert(vec![1, 2]).unwrap();
     ///
-    /// assert_eq!(heap.peek_min(), Some(5));
+    /// assert_eq!(*heap.peek_min_ref().unwrap(), vec![1, 2]);
     /// ```
-    pub fn peek_min(&self) -> Option<T> {
-        self.min.as_ref().map(|min| min.borrow().key.clone())
-    }
-
-    /// Returns a cloned copy of the minimum value without removing it
-    pub fn peek_min_cloned(&self) -> Option<T> {
-        self.min.as_ref().map(|min| min.borrow().key.clone())
+    pub fn peek_min_ref(&self) -> Option<&T> {
+        self.min.map(|min| &self.node(min).key)
     }
 
     /// Checks if the heap is empty
@@ -412,11 +1099,11 @@ impl<T: Ord + Clone> FibonacciHeap<T> {
     /// ```
     /// use fibonacci_heap::FibonacciHeap;
     ///
-    /// let heap = FibonacciHeap::new();
+    /// let heap = FibonacciHeap::<i32>::new();
     /// assert!(heap.is_empty());
     /// ```
     pub fn is_empty(&self) -> bool {
-        self.root_list.is_empty()
+        self.min.is_none()
     }
 
     /// Returns the number of nodes in the heap
@@ -440,6 +1127,8 @@ impl<T: Ord + Clone> FibonacciHeap<T> {
 
     /// Clears the heap, removing all values
     ///
+    /// Handles issued before the call are no longer valid afterwards.
+    ///
     /// # Examples
     /// ```
     /// use fibonacci_heap::FibonacciHeap;
@@ -451,17 +1140,117 @@ impl<T: Ord + Clone> FibonacciHeap<T> {
     /// assert!(heap.is_empty());
     /// ```
     pub fn clear(&mut self) {
+        self.take_segments();
+    }
+
+    /// Empties the heap and hands back the storage that held its nodes
+    fn take_segments(&mut self) -> Vec<Segment<T>> {
+        // A fresh segment gives the heap a new identity, invalidating all
+        // earlier handles even though slots will be reused from scratch
+        let segment = Segment::new();
         self.min = None;
-        self.root_list.clear();
         self.node_count = 0;
-        self.active_nodes.clear();
-        self.next_id.store(0, AtomicOrdering::SeqCst);
+        self.segment_index = HashMap::from([(segment.heap, 0)]);
+        self.free.clear();
+        self.vacant.clear();
+        std::mem::replace(&mut self.segments, vec![segment])
+    }
+}
+
+impl<T: Clone, C: Clone> Clone for FibonacciHeap<T, C> {
+    /// Creates a deep copy of the heap, including its tree structure
+    ///
+    /// Handles of the original do not resolve in the copy; use
+    /// [`clone_with_handle_map`](FibonacciHeap::clone_with_handle_map) to
+    /// translate them.
+    fn clone(&self) -> Self {
+        let segments: Vec<_> = self.segments.iter().map(Segment::fork).collect();
+        FibonacciHeap {
+            min: self.min,
+            node_count: self.node_count,
+            segment_index: segments
+                .iter()
+                .enumerate()
+                .filter(|&(position, segment)| position == 0 || segment.live > 0)
+                .map(|(position, segment)| (segment.heap, position))
+                .collect(),
+            segments,
+            free: self.free.clone(),
+            vacant: self.vacant.clone(),
+            comparator: self.comparator.clone(),
+            stats: self.stats.clone(),
+        }
+    }
+}
+
+impl<T: Clone, C: Clone> FibonacciHeap<T, C> {
+    /// Creates a deep copy of the heap and maps every live handle to its copy
+    ///
+    /// Nodes keep their positions, so the copy has exactly the same trees,
+    /// degrees and marks as the original and behaves identically from then on.
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// let node = heap.insert(10).unwrap();
+    /// heap.insert(20).unwrap();
+    ///
+    /// let (mut fork, handles) = heap.clone_with_handle_map();
+    /// fork.decrease_key(&handles[&node], 1).unwrap();
+    /// assert_eq!(fork.extract_min(), Some(1));
+    /// assert_eq!(heap.extract_min(), Some(10));
+    /// ```
+    pub fn clone_with_handle_map(&self) -> (Self, HashMap<NodeHandle, NodeHandle>) {
+        let copy = self.clone();
+        let mut handles = HashMap::with_capacity(self.node_count);
+        for (original, forked) in self.segments.iter().zip(&copy.segments) {
+            for (slot, entry) in original.slots.iter().enumerate() {
+                if entry.node.is_some() {
+                    let handle = |heap| NodeHandle {
+                        heap,
+                        slot,
+                        generation: entry.generation,
+                    };
+                    handles.insert(handle(original.heap), handle(forked.heap));
+                }
+            }
+        }
+        (copy, handles)
+    }
+}
+
+impl<T: Clone, C: Compare<T>> FibonacciHeap<T, C> {
+    /// Returns the minimum value without removing it
+    ///
+    /// # Returns
+    /// The minimum value or `None` if the heap is empty
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// heap.insert(10).unwrap();
+    /// heap.insert(5).unwrap();
+    ///
+    /// assert_eq!(heap.peek_min(), Some(5));
+    /// ```
+    pub fn peek_min(&self) -> Option<T> {
+        self.peek_min_ref().cloned()
+    }
+
+    /// Returns a cloned copy of the minimum value without removing it
+    pub fn peek_min_cloned(&self) -> Option<T> {
+        self.peek_min_ref().cloned()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::{shaped_heap, Lcg};
     use std::cmp::Ordering;
 
     #[derive(Debug, Clone, PartialEq, Eq)]
@@ -649,4 +1438,594 @@ mod tests {
         heap.decrease_key(&node2, 5).unwrap();
         heap.decrease_key(&node1, 1).unwrap();
     }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    #[should_panic(expected = "too many slots for a link")]
+    fn test_link_rejects_out_of_range_slot() {
+        let from = NodeRef {
+            segment: 0,
+            slot: 0,
+        };
+        let to = NodeRef {
+            segment: 0,
+            slot: u32::MAX as usize + 1,
+        };
+        Link::new(from, to);
+    }
+
+    #[test]
+    fn test_promoted_children_lose_marks() {
+        let (mut heap, handles) = FibonacciHeap::<i32>::from_iter_with_handles(0..32);
+        heap.extract_min();
+
+        // Mark a node below a non-minimum parent by cutting one of its children
+        let marked = (1..32)
+            .map(|i| heap.resolve(&handles[i]).unwrap())
+            .find(|&at| {
+                heap.parent(at)
+                    .is_some_and(|parent| Some(parent) != heap.min)
+                    && heap.node(at).degree >= 1
+            })
+            .unwrap();
+        let child = heap.handle(heap.first_child(marked).unwrap());
+        heap.decrease_key(&child, -1).unwrap();
+        assert!(heap.node(marked).marked);
+
+        // Raising the parent's key moves its children to the root list
+        let parent = heap.handle(heap.parent(marked).unwrap());
+        heap.increase_key(&parent, 1000).unwrap();
+        assert_eq!(heap.parent(marked), None);
+        assert!(!heap.node(marked).marked);
+    }
+
+    #[test]
+    fn test_node_handles_are_copy_and_hashable() {
+        let mut heap = FibonacciHeap::new();
+        let a = heap.insert(10).unwrap();
+        let b = heap.insert(20).unwrap();
+
+        let copy = a;
+        assert_eq!(copy, a);
+        assert_ne!(a, b);
+
+        let handles: std::collections::HashSet<NodeHandle> = [a, b, copy].into_iter().collect();
+        assert_eq!(handles.len(), 2);
+
+        heap.decrease_key(&copy, 5).unwrap();
+        assert_eq!(heap.extract_min(), Some(5));
+    }
+
+    #[test]
+    fn test_decrease_key_stale_handle() {
+        let mut heap = FibonacciHeap::new();
+        let node = heap.insert(10).unwrap();
+        heap.insert(20).unwrap();
+
+        assert_eq!(heap.extract_min(), Some(10));
+        assert_eq!(heap.decrease_key(&node, 1), Err(HeapError::NodeNotFound));
+    }
+
+    #[test]
+    fn test_delete_arbitrary_nodes() {
+        let mut heap = FibonacciHeap::new();
+        let nodes: Vec<_> = (0..20).map(|i| heap.insert(i).unwrap()).collect();
+
+        // Build some trees before deleting interior nodes
+        assert_eq!(heap.extract_min(), Some(0));
+        assert_eq!(heap.delete(&nodes[7]), Ok(7));
+        assert_eq!(heap.delete(&nodes[1]), Ok(1));
+        assert_eq!(heap.delete(&nodes[19]), Ok(19));
+        assert_eq!(heap.len(), 16);
+
+        let remaining: Vec<_> = std::iter::from_fn(|| heap.extract_min()).collect();
+        let expected: Vec<_> = (2..19).filter(|&i| i != 7).collect();
+        assert_eq!(remaining, expected);
+    }
+
+    #[test]
+    fn test_delete_stale_handle() {
+        let mut heap = FibonacciHeap::new();
+        let node = heap.insert(10).unwrap();
+        heap.insert(20).unwrap();
+
+        assert_eq!(heap.delete(&node), Ok(10));
+        assert_eq!(heap.delete(&node), Err(HeapError::NodeNotFound));
+        assert_eq!(heap.peek_min(), Some(20));
+    }
+
+    #[test]
+    fn test_increase_key() {
+        let mut heap = FibonacciHeap::new();
+        let nodes: Vec<_> = (0..10).map(|i| heap.insert(i).unwrap()).collect();
+        assert_eq!(heap.extract_min(), Some(0));
+
+        // Increase a node that has children after consolidation, and the minimum
+        heap.increase_key(&nodes[1], 15).unwrap();
+        heap.increase_key(&nodes[4], 12).unwrap();
+        assert_eq!(heap.increase_key(&nodes[5], 2), Err(HeapError::InvalidKey));
+
+        let order: Vec<_> = std::iter::from_fn(|| heap.extract_min()).collect();
+        assert_eq!(order, vec![2, 3, 5, 6, 7, 8, 9, 12, 15]);
+    }
+
+    #[test]
+    fn test_update_key_both_directions() {
+        let mut heap = FibonacciHeap::new();
+        let a = heap.insert(10).unwrap();
+        let b = heap.insert(20).unwrap();
+        let c = heap.insert(30).unwrap();
+
+        heap.update_key(&a, 40).unwrap();
+        heap.update_key(&c, 1).unwrap();
+        heap.update_key(&b, 20).unwrap();
+
+        assert_eq!(heap.extract_min(), Some(1));
+        assert_eq!(heap.extract_min(), Some(20));
+        assert_eq!(heap.update_key(&c, 0), Err(HeapError::NodeNotFound));
+        assert_eq!(heap.extract_min(), Some(40));
+    }
+
+    #[test]
+    fn test_non_clone_keys() {
+        // Neither Clone nor Copy: must be moved in and out of the heap
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Buffer(Vec<u8>);
+
+        let mut heap = FibonacciHeap::new();
+        let big = heap.insert(Buffer(vec![9; 4096])).unwrap();
+        heap.insert(Buffer(vec![5])).unwrap();
+        heap.insert(Buffer(vec![7])).unwrap();
+
+        assert_eq!(heap.peek_min_ref().map(|min| min.0.len()), Some(1));
+        assert_eq!(heap.extract_min(), Some(Buffer(vec![5])));
+
+        heap.decrease_key(&big, Buffer(vec![1])).unwrap();
+        assert_eq!(*heap.peek_min_ref().unwrap(), Buffer(vec![1]));
+        assert_eq!(heap.delete(&big), Ok(Buffer(vec![1])));
+        assert_eq!(heap.extract_min(), Some(Buffer(vec![7])));
+        assert!(heap.peek_min_ref().is_none());
+    }
+
+    #[test]
+    fn test_max_heap() {
+        let mut heap = FibonacciHeap::new_max();
+        let node = heap.insert(1).unwrap();
+        for i in [4, 9, 2, 7] {
+            heap.insert(i).unwrap();
+        }
+
+        assert_eq!(heap.extract_min(), Some(9));
+        assert_eq!(heap.decrease_key(&node, 0), Err(HeapError::InvalidKey));
+        heap.decrease_key(&node, 8).unwrap();
+
+        let order: Vec<_> = std::iter::from_fn(|| heap.extract_min()).collect();
+        assert_eq!(order, vec![8, 7, 4, 2]);
+    }
+
+    #[test]
+    fn test_closure_comparators() {
+        let mut by_priority = FibonacciHeap::new_by_key(|task: &Task| task.priority);
+        let mut reversed = FibonacciHeap::new_by(|a: &Task, b: &Task| b.name.cmp(&a.name));
+
+        for (priority, name) in [(3, "c"), (1, "b"), (2, "a")] {
+            let task = Task {
+                priority,
+                name: name.to_string(),
+            };
+            by_priority.insert(task.clone()).unwrap();
+            reversed.insert(task).unwrap();
+        }
+
+        assert_eq!(by_priority.extract_min().unwrap().name, "b");
+        assert_eq!(reversed.extract_min().unwrap().name, "c");
+    }
+
+    #[test]
+    fn test_handles_survive_merge() {
+        let mut heap1 = FibonacciHeap::new();
+        let a = heap1.insert(10).unwrap();
+
+        let mut heap2 = FibonacciHeap::new();
+        let b = heap2.insert(20).unwrap();
+        let mut heap3 = FibonacciHeap::new();
+        let c = heap3.insert(30).unwrap();
+        heap3.extract_min();
+        let c2 = heap3.insert(35).unwrap();
+
+        heap2.merge(heap3);
+        heap1.merge(heap2);
+
+        heap1.decrease_key(&c2, 1).unwrap();
+        heap1.decrease_key(&b, 2).unwrap();
+        assert_eq!(heap1.decrease_key(&c, 0), Err(HeapError::NodeNotFound));
+
+        assert_eq!(heap1.extract_min(), Some(1));
+        assert_eq!(heap1.extract_min(), Some(2));
+        assert_eq!(heap1.delete(&a), Ok(10));
+        assert!(heap1.is_empty());
+    }
+
+    #[test]
+    fn test_merge_consolidated_heaps() {
+        // Each heap gets real trees before merging
+        let mut heaps: Vec<_> = (0..4).map(|_| FibonacciHeap::new()).collect();
+        let mut handles = Vec::new();
+        for (h, heap) in heaps.iter_mut().enumerate() {
+            for i in 0..50 {
+                handles.push((heap.insert(1000 + h * 50 + i).unwrap(), 1000 + h * 50 + i));
+            }
+            heap.insert(0).unwrap();
+            assert_eq!(heap.extract_min(), Some(0));
+        }
+
+        let mut merged = heaps.pop().unwrap();
+        while let Some(mut heap) = heaps.pop() {
+            // Alternate which side absorbs the other
+            if heaps.len() % 2 == 0 {
+                heap.merge(merged);
+                merged = heap;
+            } else {
+                merged.merge(heap);
+            }
+        }
+        assert_eq!(merged.len(), 200);
+
+        // Handles from every source heap still work, including deep nodes
+        for (handle, key) in handles.iter_mut().step_by(7) {
+            *key -= 1000;
+            merged.decrease_key(handle, *key).unwrap();
+        }
+        let fresh = merged.insert(5).unwrap();
+        merged.delete(&fresh).unwrap();
+
+        let mut expected: Vec<_> = handles.iter().map(|&(_, key)| key).collect();
+        expected.sort();
+        let drained: Vec<_> = std::iter::from_fn(|| merged.extract_min()).collect();
+        assert_eq!(drained, expected);
+        assert_eq!(
+            merged.decrease_key(&handles[1].0, 0),
+            Err(HeapError::NodeNotFound)
+        );
+    }
+
+    #[test]
+    fn test_merge_releases_emptied_segments() {
+        // Absorbing one-element heaps and extracting them again
+        let mut heap = FibonacciHeap::new();
+        let kept = heap.insert(100).unwrap();
+        for i in 0..10_000 {
+            let mut other = FibonacciHeap::new();
+            other.insert(i % 7).unwrap();
+            heap.merge(other);
+            assert_eq!(heap.extract_min(), Some(i % 7));
+            assert!(heap.segments.len() <= 2 && heap.segment_index.len() <= 2);
+        }
+        assert_eq!(heap.segments.len(), 1);
+        heap.decrease_key(&kept, 50).unwrap();
+
+        // Being absorbed by a fresh heap every time instead
+        let mut heap = FibonacciHeap::new();
+        let kept = heap.insert(1_000_000).unwrap();
+        for i in 0..10_000 {
+            let mut absorber = FibonacciHeap::new();
+            absorber.insert(i).unwrap();
+            absorber.merge(heap);
+            heap = absorber;
+            assert_eq!(heap.extract_min(), Some(i));
+            assert!(heap.segments.len() <= 2 && heap.segment_index.len() <= 2);
+        }
+        heap.validate().unwrap();
+        assert_eq!(heap.clone().segments.len(), heap.segments.len());
+        heap.decrease_key(&kept, -1).unwrap();
+        assert_eq!(heap.extract_min(), Some(-1));
+        assert!(heap.is_empty());
+
+        // An emptied segment between live ones leaves a hole for the next merge
+        let (mut a, mut b, mut c) = (
+            FibonacciHeap::new(),
+            FibonacciHeap::new(),
+            FibonacciHeap::new(),
+        );
+        let (ha, hb, hc) = (
+            a.insert(1).unwrap(),
+            b.insert(2).unwrap(),
+            c.insert(3).unwrap(),
+        );
+        a.merge(b);
+        a.merge(c);
+        assert_eq!(a.delete(&hb), Ok(2));
+        let mut heap = FibonacciHeap::new();
+        heap.insert(10).unwrap();
+        heap.merge(a);
+        assert_eq!(heap.segments.len(), 4);
+        let mut single = FibonacciHeap::new();
+        let hs = single.insert(4).unwrap();
+        heap.merge(single);
+        assert_eq!(heap.segments.len(), 4);
+        heap.validate().unwrap();
+
+        heap.decrease_key(&hc, 0).unwrap();
+        assert_eq!(heap.delete(&hb), Err(HeapError::NodeNotFound));
+        let drained: Vec<_> = std::iter::from_fn(|| heap.extract_min()).collect();
+        assert_eq!(drained, [0, 1, 4, 10]);
+        assert_eq!(heap.delete(&ha), Err(HeapError::NodeNotFound));
+        assert_eq!(heap.delete(&hs), Err(HeapError::NodeNotFound));
+        assert_eq!(heap.segments.len(), 1);
+
+        // Storage of emptied heaps goes as soon as no later segment needs the spacing
+        let mut heap = FibonacciHeap::new();
+        let handles: Vec<_> = (0..100)
+            .map(|i| {
+                let mut other = FibonacciHeap::new();
+                let handle = other.insert(i).unwrap();
+                heap.merge(other);
+                handle
+            })
+            .collect();
+        assert_eq!(heap.segments.len(), 101);
+        for (i, handle) in handles.iter().enumerate().skip(50).rev() {
+            heap.delete(handle).unwrap();
+            assert_eq!(heap.segments.len(), i + 1);
+        }
+        for handle in &handles[..49] {
+            heap.delete(handle).unwrap();
+            assert_eq!(heap.segments.len(), 51);
+        }
+        heap.delete(&handles[49]).unwrap();
+        assert_eq!((heap.segments.len(), heap.vacant.len()), (1, 0));
+
+        // So absorbing a heap that has emptied everything it merged is cheap
+        let mut absorber = FibonacciHeap::new();
+        heap.insert(7).unwrap();
+        absorber.merge(heap);
+        assert_eq!(absorber.segments.len(), 2);
+    }
+
+    #[test]
+    fn test_cascading_cuts_on_large_heap() {
+        let mut heap = FibonacciHeap::new();
+        let handles: Vec<_> = (0..10_000).map(|i| heap.insert(i).unwrap()).collect();
+        assert_eq!(heap.extract_min(), Some(0));
+
+        // Cut many children out of deep trees, marking and cutting their parents
+        for (i, handle) in handles.iter().enumerate().skip(1).step_by(3) {
+            heap.decrease_key(handle, -(i as i64)).unwrap();
+        }
+
+        let drained: Vec<_> = std::iter::from_fn(|| heap.extract_min()).collect();
+        assert_eq!(drained.len(), 9_999);
+        assert!(drained.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_reused_slot_rejects_stale_handle() {
+        let mut heap = FibonacciHeap::new();
+        let old = heap.insert(10).unwrap();
+        assert_eq!(heap.extract_min(), Some(10));
+
+        // The new node takes over the freed slot
+        let new = heap.insert(20).unwrap();
+        assert_ne!(old, new);
+        assert_eq!(heap.decrease_key(&old, 5), Err(HeapError::NodeNotFound));
+        assert_eq!(heap.peek_min(), Some(20));
+    }
+
+    #[test]
+    fn test_clear_invalidates_handles() {
+        let mut heap = FibonacciHeap::new();
+        let old = heap.insert(10).unwrap();
+        heap.clear();
+
+        // The new node lands in the same slot with the same generation count
+        let new = heap.insert(20).unwrap();
+        assert_ne!(old, new);
+        assert_eq!(heap.decrease_key(&old, 1), Err(HeapError::NodeNotFound));
+        assert_eq!(heap.delete(&old), Err(HeapError::NodeNotFound));
+        assert_eq!(heap.extract_min(), Some(20));
+    }
+
+    #[test]
+    fn test_foreign_handles_rejected() {
+        let mut heap1 = FibonacciHeap::new();
+        let mut heap2 = FibonacciHeap::new();
+        let own = heap1.insert(10).unwrap();
+        let foreign = heap2.insert(20).unwrap();
+
+        assert_eq!(
+            heap1.decrease_key(&foreign, 1),
+            Err(HeapError::NodeNotFound)
+        );
+        assert_eq!(heap2.delete(&own), Err(HeapError::NodeNotFound));
+
+        // Merging a third heap does not make heap2's handles valid in heap1
+        let mut heap3 = FibonacciHeap::new();
+        heap3.insert(30).unwrap();
+        heap1.merge(heap3);
+        assert_eq!(
+            heap1.decrease_key(&foreign, 1),
+            Err(HeapError::NodeNotFound)
+        );
+        assert_eq!(heap2.extract_min(), Some(20));
+        assert_eq!(heap1.len(), 2);
+    }
+
+    #[test]
+    fn test_wrapped_generation_retires_slot() {
+        let mut heap = FibonacciHeap::new();
+        let handle = heap.insert(1).unwrap();
+        heap.segments[0].slots[handle.slot].generation = u32::MAX;
+        let handle = NodeHandle {
+            generation: u32::MAX,
+            ..handle
+        };
+        assert_eq!(heap.extract_min(), Some(1));
+
+        // The wrapped slot must not be reused
+        let next = heap.insert(2).unwrap();
+        assert_ne!(next.slot, handle.slot);
+        assert_eq!(heap.delete(&handle), Err(HeapError::NodeNotFound));
+    }
+
+    #[test]
+    fn test_clone_preserves_structure() {
+        let (mut heap, handles) = shaped_heap();
+        let mut other = FibonacciHeap::new();
+        let adopted = other.insert(100).unwrap();
+        heap.merge(other);
+        // Consolidate again so that trees span both segments
+        heap.insert(-10).unwrap();
+        assert_eq!(heap.extract_min(), Some(-10));
+
+        let (mut fork, map) = heap.clone_with_handle_map();
+        assert_eq!(map.len(), heap.len());
+        assert_eq!(fork.min, heap.min);
+        for (original, copy) in heap.segments.iter().zip(&fork.segments) {
+            assert_ne!(original.heap, copy.heap);
+            for (a, b) in original.slots.iter().zip(&copy.slots) {
+                let (a, b) = (a.node.as_ref(), b.node.as_ref());
+                assert_eq!(
+                    a.map(|n| (n.key, n.degree, n.marked)),
+                    b.map(|n| (n.key, n.degree, n.marked))
+                );
+                assert_eq!(
+                    a.map(|n| (n.parent, n.child, n.left, n.right)),
+                    b.map(|n| (n.parent, n.child, n.left, n.right))
+                );
+            }
+        }
+
+        // Original handles, including adopted ones, do not reach into the fork
+        assert_eq!(fork.delete(&handles[10]), Err(HeapError::NodeNotFound));
+        assert_eq!(fork.delete(&adopted), Err(HeapError::NodeNotFound));
+        assert_eq!(fork.delete(&map[&adopted]), Ok(100));
+        fork.decrease_key(&map[&handles[63]], -3).unwrap();
+
+        assert_eq!(heap.extract_min(), Some(-1));
+        assert_eq!(fork.extract_min(), Some(-3));
+        assert_eq!(heap.len(), 62);
+        assert_eq!(fork.clone().into_sorted_vec().len(), 61);
+    }
+
+    /// Orders `(priority, id)` jobs by priority alone, leaving ties to the heap
+    fn stable_jobs() -> FibonacciHeap<(u32, usize), impl Compare<(u32, usize)>> {
+        FibonacciHeap::with_comparator(Stable(KeyComparator(|job: &(u32, usize)| job.0)))
+    }
+
+    /// Checks that jobs of equal priority come out in increasing id order
+    fn assert_fifo(order: &[(u32, usize)]) {
+        for pair in order.windows(2) {
+            assert!(
+                pair[0].0 < pair[1].0 || (pair[0].0 == pair[1].0 && pair[0].1 < pair[1].1),
+                "{pair:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_stable_order_under_inserts_and_extracts() {
+        let mut heap = stable_jobs();
+        let mut order = Vec::new();
+        for id in 0..600 {
+            heap.insert(((id as u32 * 7) % 5, id)).unwrap();
+            // Interleaved extractions force consolidation between inserts
+            if id % 50 == 49 {
+                order.push(heap.extract_min().unwrap());
+            }
+        }
+        let rest: Vec<_> = heap.drain_sorted().collect();
+        assert_fifo(&rest);
+        order.extend(rest);
+
+        // Each priority is served in insertion order across both phases
+        for priority in 0..5 {
+            let ids: Vec<_> = order
+                .iter()
+                .filter(|job| job.0 == priority)
+                .map(|job| job.1)
+                .collect();
+            assert_eq!(ids.len(), 120);
+            assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn test_stable_order_across_merge() {
+        let mut first = stable_jobs();
+        let mut second = stable_jobs();
+        for id in 0..200 {
+            let heap = if id % 3 == 0 { &mut second } else { &mut first };
+            heap.insert((id as u32 % 4, id)).unwrap();
+        }
+        first.extract_min();
+        second.extract_min();
+        first.merge(second);
+
+        let order = first.into_sorted_vec();
+        assert_eq!(order.len(), 198);
+        assert_fifo(&order);
+    }
+
+    #[test]
+    fn test_stable_order_after_decrease_key() {
+        let mut heap = stable_jobs();
+        let handles: Vec<_> = (0..100)
+            .map(|id| heap.insert((50 + id as u32, id)).unwrap())
+            .collect();
+        heap.insert((10, 100)).unwrap();
+        heap.insert((0, 101)).unwrap();
+        assert_eq!(heap.extract_min(), Some((0, 101)));
+
+        // Later jobs join the earlier ones' priority and must still queue behind them
+        for id in (0..100).rev().step_by(3) {
+            heap.decrease_key(&handles[id], (10, id)).unwrap();
+        }
+        let order = heap.into_sorted_vec();
+        assert_fifo(&order);
+        assert_eq!(order[..3], [(10, 0), (10, 3), (10, 6)]);
+        assert_eq!(order.iter().position(|job| job.1 == 100), Some(34));
+    }
+
+    #[test]
+    fn test_randomized_operations_match_reference() {
+        let mut rng = Lcg::new(0x2545_f491_4f6c_dd1d);
+        let mut next = |bound: u64| rng.next(bound);
+
+        // Keys carry a unique tag so the reference knows exactly which node left
+        let mut heap = FibonacciHeap::new();
+        let mut live: Vec<(NodeHandle, (i64, usize))> = Vec::new();
+
+        for tag in 0..5000 {
+            match next(10) {
+                0..=3 => {
+                    let key = (next(1000) as i64, tag);
+                    live.push((heap.insert(key).unwrap(), key));
+                }
+                4 | 5 => {
+                    let min = live.iter().map(|&(_, key)| key).min();
+                    assert_eq!(heap.extract_min(), min);
+                    live.retain(|&(_, key)| Some(key) != min);
+                }
+                6 | 7 if !live.is_empty() => {
+                    let pos = next(live.len() as u64) as usize;
+                    let key = (live[pos].1 .0 - next(100) as i64, live[pos].1 .1);
+                    heap.decrease_key(&live[pos].0, key).unwrap();
+                    live[pos].1 = key;
+                }
+                8 if !live.is_empty() => {
+                    let pos = next(live.len() as u64) as usize;
+                    let key = (live[pos].1 .0 + next(100) as i64, live[pos].1 .1);
+                    heap.update_key(&live[pos].0, key).unwrap();
+                    live[pos].1 = key;
+                }
+                9 if !live.is_empty() => {
+                    let pos = next(live.len() as u64) as usize;
+                    let (handle, key) = live.swap_remove(pos);
+                    assert_eq!(heap.delete(&handle), Ok(key));
+                }
+                _ => {}
+            }
+            assert_eq!(heap.len(), live.len());
+        }
+    }
 }
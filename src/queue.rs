@@ -0,0 +1,284 @@
+//! Priority queue traits, so callers can swap heap implementations.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::{Compare, FibonacciHeap, HeapError, KeyedFibonacciHeap, NodeHandle};
+
+/// A queue that hands out its items in priority order
+///
+/// Which item comes first is up to the implementation: [`FibonacciHeap`]
+/// follows its comparator, [`BinaryHeap`] pops the largest item, and
+/// [`MinBinaryHeap`] pops the smallest.
+///
+/// # Examples
+/// ```
+/// use fibonacci_heap::{FibonacciHeap, MinBinaryHeap, PriorityQueue};
+///
+/// fn sorted<Q: PriorityQueue<Item = u32>>(mut queue: Q, items: &[u32]) -> Vec<u32> {
+///     for &item in items {
+///         queue.push(item);
+///     }
+///     std::iter::from_fn(|| queue.pop()).collect()
+/// }
+///
+/// let items = [5, 1, 4, 2, 3];
+/// assert_eq!(sorted(FibonacciHeap::new(), &items), [1, 2, 3, 4, 5]);
+/// assert_eq!(sorted(MinBinaryHeap::new(), &items), [1, 2, 3, 4, 5]);
+/// ```
+pub trait PriorityQueue {
+    /// The type of the queued items
+    type Item;
+
+    /// Adds an item to the queue
+    fn push(&mut self, item: Self::Item);
+
+    /// Removes and returns the first item
+    fn pop(&mut self) -> Option<Self::Item>;
+
+    /// Borrows the first item without removing it
+    fn peek(&self) -> Option<&Self::Item>;
+
+    /// Returns the number of queued items
+    fn len(&self) -> usize;
+
+    /// Checks if the queue is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A priority queue whose items can be reached again after insertion
+///
+/// [`BinaryHeap`] moves its items around freely and cannot hand out stable
+/// handles, so it only implements [`PriorityQueue`].
+///
+/// # Examples
+/// ```
+/// use fibonacci_heap::{AddressablePriorityQueue, FibonacciHeap, PriorityQueue};
+///
+/// fn promote<Q: AddressablePriorityQueue<Item = u32>>(queue: &mut Q) -> Option<u32> {
+///     queue.push(10);
+///     let late = queue.push_with_handle(50);
+///     queue.decrease_key(&late, 1).ok()?;
+///     queue.pop()
+/// }
+///
+/// assert_eq!(promote(&mut FibonacciHeap::new()), Some(1));
+/// ```
+pub trait AddressablePriorityQueue: PriorityQueue {
+    /// Identifies a queued item
+    type Handle;
+
+    /// Adds an item to the queue and returns a handle to it
+    fn push_with_handle(&mut self, item: Self::Item) -> Self::Handle;
+
+    /// Replaces the item behind a handle with one that comes earlier
+    ///
+    /// Returns `HeapError::InvalidKey` if the new item would come later, and
+    /// `HeapError::NodeNotFound` if the handle no longer refers to an item.
+    fn decrease_key(&mut self, handle: &Self::Handle, item: Self::Item) -> Result<(), HeapError>;
+
+    /// Removes the item behind a handle and returns it
+    fn delete(&mut self, handle: &Self::Handle) -> Result<Self::Item, HeapError>;
+
+    /// Moves all items of `other` into this queue
+    ///
+    /// Handles from `other` stay valid and refer to the same items here.
+    fn merge(&mut self, other: Self)
+    where
+        Self: Sized;
+}
+
+impl<T, C: Compare<T>> PriorityQueue for FibonacciHeap<T, C> {
+    type Item = T;
+
+    fn push(&mut self, item: T) {
+        self.insert(item)
+            .expect("inserting into a heap cannot fail");
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.extract_min()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek_min_ref()
+    }
+
+    fn len(&self) -> usize {
+        FibonacciHeap::len(self)
+    }
+}
+
+impl<T, C: Compare<T>> AddressablePriorityQueue for FibonacciHeap<T, C> {
+    type Handle = NodeHandle;
+
+    fn push_with_handle(&mut self, item: T) -> NodeHandle {
+        self.insert(item)
+            .expect("inserting into a heap cannot fail")
+    }
+
+    fn decrease_key(&mut self, handle: &NodeHandle, item: T) -> Result<(), HeapError> {
+        FibonacciHeap::decrease_key(self, handle, item)
+    }
+
+    fn delete(&mut self, handle: &NodeHandle) -> Result<T, HeapError> {
+        FibonacciHeap::delete(self, handle)
+    }
+
+    fn merge(&mut self, other: Self) {
+        FibonacciHeap::merge(self, other)
+    }
+}
+
+/// Queues `(priority, value)` pairs, ordered by priority only
+impl<P, V, C: Compare<P>> PriorityQueue for KeyedFibonacciHeap<P, V, C> {
+    type Item = (P, V);
+
+    fn push(&mut self, (priority, value): (P, V)) {
+        self.insert(priority, value)
+            .expect("inserting into a heap cannot fail");
+    }
+
+    fn pop(&mut self) -> Option<(P, V)> {
+        self.extract_min()
+    }
+
+    fn peek(&self) -> Option<&(P, V)> {
+        self.peek_min_ref()
+    }
+
+    fn len(&self) -> usize {
+        KeyedFibonacciHeap::len(self)
+    }
+}
+
+/// Pops the largest item first, like [`FibonacciHeap::new_max`]
+impl<T: Ord> PriorityQueue for BinaryHeap<T> {
+    type Item = T;
+
+    fn push(&mut self, item: T) {
+        BinaryHeap::push(self, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        BinaryHeap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        BinaryHeap::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        BinaryHeap::len(self)
+    }
+}
+
+/// A [`BinaryHeap`] that pops the smallest item first, like [`FibonacciHeap::new`]
+///
+/// Useful as a drop-in [`PriorityQueue`] wherever a min-heap is expected.
+#[derive(Debug, Clone)]
+pub struct MinBinaryHeap<T>(BinaryHeap<Reverse<T>>);
+
+impl<T: Ord> MinBinaryHeap<T> {
+    /// Creates a new empty heap
+    pub fn new() -> Self {
+        MinBinaryHeap(BinaryHeap::new())
+    }
+
+    /// Returns the underlying [`BinaryHeap`]
+    pub fn into_inner(self) -> BinaryHeap<Reverse<T>> {
+        self.0
+    }
+}
+
+impl<T: Ord> Default for MinBinaryHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> PriorityQueue for MinBinaryHeap<T> {
+    type Item = T;
+
+    fn push(&mut self, item: T) {
+        self.0.push(Reverse(item));
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.0.pop().map(|Reverse(item)| item)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.0.peek().map(|Reverse(item)| item)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pushes pseudo-random items, interleaving pops, and returns everything popped
+    fn churn<Q: PriorityQueue<Item = u32>>(queue: &mut Q) -> Vec<u32> {
+        let mut popped = Vec::new();
+        for i in 0..500u32 {
+            queue.push(i.wrapping_mul(2_654_435_761) % 1000);
+            if i % 3 == 0 {
+                let first = queue.peek().copied();
+                assert_eq!(queue.pop(), first);
+                popped.extend(first);
+            }
+        }
+        while let Some(item) = queue.pop() {
+            popped.push(item);
+        }
+        assert!(queue.is_empty() && queue.peek().is_none());
+        popped
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        let min = churn(&mut FibonacciHeap::new());
+        assert_eq!(min, churn(&mut MinBinaryHeap::new()));
+        let max = churn(&mut FibonacciHeap::new_max());
+        assert_eq!(max, churn(&mut BinaryHeap::new()));
+        assert_ne!(min, max);
+
+        let mut keyed = KeyedFibonacciHeap::new();
+        keyed.push((2, "b"));
+        keyed.push((1, "a"));
+        assert_eq!(PriorityQueue::len(&keyed), 2);
+        assert_eq!(keyed.pop(), Some((1, "a")));
+    }
+
+    /// Uses a queue only through the addressable trait
+    fn addressable<Q: AddressablePriorityQueue<Item = i32>>(mut queue: Q, mut other: Q) {
+        let handles: Vec<_> = (0..10).map(|i| queue.push_with_handle(i * 10)).collect();
+        let adopted = other.push_with_handle(55);
+        queue.merge(other);
+
+        assert_eq!(
+            queue.decrease_key(&handles[4], 45),
+            Err(HeapError::InvalidKey)
+        );
+        queue.decrease_key(&handles[7], -1).unwrap();
+        queue.decrease_key(&adopted, 5).unwrap();
+        assert_eq!(queue.delete(&handles[0]), Ok(0));
+        assert_eq!(queue.delete(&handles[0]), Err(HeapError::NodeNotFound));
+
+        assert_eq!(queue.len(), 10);
+        assert_eq!(queue.pop(), Some(-1));
+        assert_eq!(queue.pop(), Some(5));
+        assert_eq!(queue.pop(), Some(10));
+    }
+
+    #[test]
+    fn test_addressable_fibonacci_heap() {
+        addressable(FibonacciHeap::new(), FibonacciHeap::new());
+    }
+}
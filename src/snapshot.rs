@@ -0,0 +1,273 @@
+//! Serde support for saving and restoring heaps, available with the `serde` feature.
+//!
+//! A heap is written as a flat sequence of `(handle, key)` entries; the tree
+//! shape is rebuilt lazily by the next `extract_min` after restoring. Heaps
+//! with a [`Stable`](crate::Stable) comparator are written in insertion
+//! order, so ties still resolve the same way once restored. The comparator
+//! is not stored, so restoring requires `C: Default`.
+//!
+//! Restored nodes get new handles. Handles read back from storage are
+//! [`SavedHandle`]s, which no heap accepts; they must be translated with the
+//! map returned by [`FibonacciHeap::deserialize_with_handles`], since heap
+//! identities are reused by unrelated heaps after a restart.
+
+use std::collections::HashMap;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Compare, FibonacciHeap, NodeHandle, NodeRef};
+
+impl Serialize for NodeHandle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.heap, self.slot, self.generation).serialize(serializer)
+    }
+}
+
+/// A [`NodeHandle`] as recorded in a snapshot
+///
+/// Handles are written as plain [`NodeHandle`]s but can only be read back as
+/// `SavedHandle`s. Heap identities are only unique within one process, so a
+/// handle restored as-is could match a different heap and reach the wrong
+/// node. A saved handle is not accepted by any heap; it is only a key into
+/// the map returned by [`FibonacciHeap::deserialize_with_handles`].
+///
+/// # Examples
+/// ```compile_fail
+/// use fibonacci_heap::{FibonacciHeap, SavedHandle};
+///
+/// let mut heap = FibonacciHeap::new();
+/// let saved = SavedHandle::from(heap.insert(1).unwrap());
+/// heap.delete(&saved).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SavedHandle {
+    heap: usize,
+    slot: usize,
+    generation: u32,
+}
+
+impl From<NodeHandle> for SavedHandle {
+    fn from(handle: NodeHandle) -> Self {
+        SavedHandle {
+            heap: handle.heap,
+            slot: handle.slot,
+            generation: handle.generation,
+        }
+    }
+}
+
+impl Serialize for SavedHandle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.heap, self.slot, self.generation).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SavedHandle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (heap, slot, generation) = Deserialize::deserialize(deserializer)?;
+        Ok(SavedHandle {
+            heap,
+            slot,
+            generation,
+        })
+    }
+}
+
+impl<T: Serialize, C: Compare<T>> Serialize for FibonacciHeap<T, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let nodes = self
+            .segments
+            .iter()
+            .enumerate()
+            .flat_map(|(segment, storage)| {
+                storage
+                    .slots
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(slot, entry)| {
+                        let node = entry.node.as_ref()?;
+                        Some((self.handle(NodeRef { segment, slot }), node))
+                    })
+            });
+
+        if C::STABLE {
+            // Restoring inserts entries in order, so write them in insertion order
+            let mut nodes: Vec<_> = nodes.collect();
+            nodes.sort_unstable_by_key(|(_, node)| node.sequence);
+            serializer.collect_seq(nodes.into_iter().map(|(handle, node)| (handle, &node.key)))
+        } else {
+            serializer.collect_seq(nodes.map(|(handle, node)| (handle, &node.key)))
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>, C: Compare<T> + Default> Deserialize<'de> for FibonacciHeap<T, C> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::deserialize_with_handles(deserializer).map(|(heap, _)| heap)
+    }
+}
+
+impl<T, C: Compare<T> + Default> FibonacciHeap<T, C> {
+    /// Restores a heap and maps each handle recorded in the snapshot to its new handle
+    ///
+    /// Handles kept in memory since the snapshot was taken are looked up with
+    /// [`SavedHandle::from`]. Fails if the snapshot lists the same handle twice.
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::{FibonacciHeap, SavedHandle};
+    ///
+    /// let mut heap = FibonacciHeap::<i32>::new();
+    /// let node = heap.insert(10).unwrap();
+    /// let saved = serde_json::to_string(&(&heap, node)).unwrap();
+    ///
+    /// // Possibly in another process
+    /// let (snapshot, node): (serde_json::Value, SavedHandle) = serde_json::from_str(&saved).unwrap();
+    /// let (mut restored, handles) = FibonacciHeap::<i32>::deserialize_with_handles(snapshot).unwrap();
+    /// restored.decrease_key(&handles[&node], 1).unwrap();
+    /// assert_eq!(restored.extract_min(), Some(1));
+    /// ```
+    pub fn deserialize_with_handles<'de, D>(
+        deserializer: D,
+    ) -> Result<(Self, HashMap<SavedHandle, NodeHandle>), D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let entries = Vec::<(SavedHandle, T)>::deserialize(deserializer)?;
+        Self::restore_into(Self::default(), entries).map_err(|old| {
+            D::Error::custom(format_args!(
+                "duplicate node handle {old:?} in heap snapshot"
+            ))
+        })
+    }
+
+    /// Inserts snapshot entries into `heap`, returning the first repeated handle on failure
+    fn restore_into(
+        mut heap: Self,
+        entries: Vec<(SavedHandle, T)>,
+    ) -> Result<(Self, HashMap<SavedHandle, NodeHandle>), SavedHandle> {
+        let (old, keys): (Vec<SavedHandle>, Vec<T>) = entries.into_iter().unzip();
+        let new = heap.extend_with_handles(keys);
+
+        let mut handles = HashMap::with_capacity(old.len());
+        for (old, new) in old.into_iter().zip(new) {
+            if handles.insert(old, new).is_some() {
+                return Err(old);
+            }
+        }
+        Ok((heap, handles))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SavedHandle;
+    use crate::{FibonacciHeap, HeapError, KeyedFibonacciHeap, MaxOrder, Stable};
+
+    #[test]
+    fn test_round_trip_remaps_handles() {
+        let mut heap = FibonacciHeap::new();
+        let mut other = FibonacciHeap::new();
+        let handles: Vec<_> = (0..10).map(|i| heap.insert(i * 10).unwrap()).collect();
+        let adopted = other.insert(55).unwrap();
+        heap.merge(other);
+        assert_eq!(heap.extract_min(), Some(0));
+        heap.decrease_key(&handles[7], 5).unwrap();
+
+        let saved = serde_json::to_string(&heap).unwrap();
+        let mut json = serde_json::Deserializer::from_str(&saved);
+        let (mut restored, map) =
+            FibonacciHeap::<i32>::deserialize_with_handles(&mut json).unwrap();
+        assert_eq!(map.len(), 10);
+        assert!(!map.contains_key(&handles[0].into()));
+
+        // Old handles do not resolve in the restored heap, mapped ones do
+        assert_eq!(restored.delete(&handles[9]), Err(HeapError::NodeNotFound));
+        assert_eq!(restored.delete(&map[&handles[9].into()]), Ok(90));
+        restored.decrease_key(&map[&adopted.into()], 1).unwrap();
+
+        assert_eq!(
+            restored.into_sorted_vec(),
+            vec![1, 5, 10, 20, 30, 40, 50, 60, 80]
+        );
+    }
+
+    #[test]
+    fn test_restores_with_default_comparator() {
+        let heap: FibonacciHeap<_, MaxOrder> = vec![3, 8, 1].into();
+        let saved = serde_json::to_value(&heap).unwrap();
+        let restored: FibonacciHeap<i32, MaxOrder> = serde_json::from_value(saved).unwrap();
+        assert_eq!(restored.into_sorted_vec(), vec![8, 3, 1]);
+
+        let mut keyed = KeyedFibonacciHeap::new();
+        let job = keyed.insert(4, String::from("backup")).unwrap();
+        keyed.insert(2, String::from("index")).unwrap();
+        let saved = serde_json::to_string(&keyed).unwrap();
+        let mut json = serde_json::Deserializer::from_str(&saved);
+        let (mut restored, map) =
+            KeyedFibonacciHeap::<i32, String>::deserialize_with_handles(&mut json).unwrap();
+        restored.decrease_key(&map[&job.into()], 0).unwrap();
+        assert_eq!(restored.extract_min(), Some((0, String::from("backup"))));
+    }
+
+    #[test]
+    fn test_restart_reusing_the_heap_id() {
+        // The saved heap holds 20 in slot 1; restoring moves 30 there
+        let mut heap = FibonacciHeap::new();
+        let handles: Vec<_> = (1..4).map(|i| heap.insert(i * 10).unwrap()).collect();
+        heap.delete(&handles[0]).unwrap();
+        let mut saved = serde_json::to_value(&heap).unwrap();
+
+        // Pretend the snapshot came from an earlier process whose heap had
+        // the same identity as the one restored into now
+        let target = FibonacciHeap::new();
+        for entry in saved.as_array_mut().unwrap() {
+            entry[0][0] = target.segments[0].heap.into();
+        }
+        let entries: Vec<(SavedHandle, i32)> = serde_json::from_value(saved).unwrap();
+        let (old_twenty, old_thirty) = (entries[0].0, entries[1].0);
+        let (mut restored, map) = FibonacciHeap::restore_into(target, entries).unwrap();
+
+        // The live handle to 30 looks exactly like the saved handle to 20,
+        // but saved handles only act through the map
+        assert_eq!(SavedHandle::from(map[&old_thirty]), old_twenty);
+        assert_eq!(restored.delete(&map[&old_twenty]), Ok(20));
+        assert_eq!(
+            restored.delete(&map[&old_twenty]),
+            Err(HeapError::NodeNotFound)
+        );
+        assert_eq!(restored.into_sorted_vec(), vec![30]);
+    }
+
+    #[test]
+    fn test_duplicate_handles_rejected() {
+        let mut heap = FibonacciHeap::new();
+        heap.insert(1).unwrap();
+        let entry = serde_json::to_value(&heap).unwrap()[0].clone();
+        let snapshot = serde_json::Value::Array(vec![entry.clone(), entry]);
+
+        let error = serde_json::from_value::<FibonacciHeap<i32>>(snapshot).unwrap_err();
+        assert!(error.to_string().contains("duplicate node handle"));
+    }
+
+    #[test]
+    fn test_stable_heap_keeps_tie_order() {
+        let mut heap = KeyedFibonacciHeap::<_, _, Stable>::default();
+        let handles: Vec<_> = (0..20).map(|id| heap.insert(1, id).unwrap()).collect();
+        // Free early slots so that arena order no longer matches insertion order
+        for handle in &handles[..5] {
+            heap.delete(handle).unwrap();
+        }
+        heap.insert(1, 20).unwrap();
+
+        let saved = serde_json::to_string(&heap).unwrap();
+        let mut restored: KeyedFibonacciHeap<i32, i32, Stable> =
+            serde_json::from_str(&saved).unwrap();
+        let order: Vec<_> = std::iter::from_fn(|| restored.extract_min())
+            .map(|(_, id)| id)
+            .collect();
+        assert_eq!(order, (5..21).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,226 @@
+//! Counters for the work a heap performs, available with the `stats` feature.
+//!
+//! Without the feature the collector is a zero-sized no-op, so the hooks in
+//! the heap cost nothing.
+
+pub(crate) use collector::Collector;
+#[cfg(feature = "stats")]
+pub use collector::HeapStats;
+
+#[cfg(feature = "stats")]
+mod collector {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use crate::{Compare, FibonacciHeap};
+
+    /// Counts of the internal work done by a [`FibonacciHeap`]
+    ///
+    /// Returned by [`FibonacciHeap::stats`]. Counters accumulate until
+    /// [`FibonacciHeap::reset_stats`] is called; merging adds the other
+    /// heap's counters to this one's.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct HeapStats {
+        /// Trees linked under another root while consolidating
+        pub links: u64,
+        /// Nodes cut from their parent, including cascading cuts
+        pub cuts: u64,
+        /// Cuts of marked ancestors triggered by an earlier cut
+        pub cascading_cuts: u64,
+        /// Consolidation passes over the root list
+        pub consolidations: u64,
+        /// Comparisons between nodes
+        pub comparisons: u64,
+        /// Highest degree any node has reached
+        pub max_degree: usize,
+        /// Longest root list seen at the start of a consolidation
+        pub max_root_list_len: usize,
+        /// Current number of roots
+        pub root_list_len: usize,
+    }
+
+    #[derive(Debug, Default)]
+    pub(crate) struct Collector {
+        stats: HeapStats,
+        comparisons: AtomicU64, // Counted from `&self` methods
+    }
+
+    impl Clone for Collector {
+        fn clone(&self) -> Self {
+            Collector {
+                stats: self.stats,
+                comparisons: AtomicU64::new(self.comparisons.load(Ordering::Relaxed)),
+            }
+        }
+    }
+
+    impl Collector {
+        pub(crate) fn link(&mut self, parent_degree: usize) {
+            self.stats.links += 1;
+            self.stats.max_degree = self.stats.max_degree.max(parent_degree);
+        }
+
+        pub(crate) fn cut(&mut self) {
+            self.stats.cuts += 1;
+        }
+
+        pub(crate) fn cascading_cut(&mut self) {
+            self.stats.cascading_cuts += 1;
+        }
+
+        pub(crate) fn consolidation(&mut self, roots: usize) {
+            self.stats.consolidations += 1;
+            self.stats.max_root_list_len = self.stats.max_root_list_len.max(roots);
+        }
+
+        pub(crate) fn comparison(&self) {
+            // Only the owning heap writes, so a plain load and store suffice
+            let count = self.comparisons.load(Ordering::Relaxed);
+            self.comparisons.store(count + 1, Ordering::Relaxed);
+        }
+
+        pub(crate) fn absorb(&mut self, other: &Collector) {
+            let other = other.snapshot();
+            self.stats.links += other.links;
+            self.stats.cuts += other.cuts;
+            self.stats.cascading_cuts += other.cascading_cuts;
+            self.stats.consolidations += other.consolidations;
+            self.stats.max_degree = self.stats.max_degree.max(other.max_degree);
+            self.stats.max_root_list_len =
+                self.stats.max_root_list_len.max(other.max_root_list_len);
+            *self.comparisons.get_mut() += other.comparisons;
+        }
+
+        fn snapshot(&self) -> HeapStats {
+            HeapStats {
+                comparisons: self.comparisons.load(Ordering::Relaxed),
+                ..self.stats
+            }
+        }
+    }
+
+    impl<T, C: Compare<T>> FibonacciHeap<T, C> {
+        /// Returns the counters collected since creation or the last reset
+        ///
+        /// `root_list_len` is measured on the spot, which takes time linear
+        /// in the number of roots.
+        ///
+        /// # Examples
+        /// ```
+        /// use fibonacci_heap::FibonacciHeap;
+        ///
+        /// let mut heap: FibonacciHeap<_> = (0..8).collect();
+        /// heap.extract_min();
+        ///
+        /// let stats = heap.stats();
+        /// assert_eq!(stats.consolidations, 1);
+        /// assert_eq!(stats.links, 4);
+        /// assert_eq!(stats.root_list_len, 3);
+        /// ```
+        pub fn stats(&self) -> HeapStats {
+            HeapStats {
+                root_list_len: self.min.map_or(0, |min| self.ring_members(min).len()),
+                ..self.stats.snapshot()
+            }
+        }
+
+        /// Sets all counters back to zero
+        pub fn reset_stats(&mut self) {
+            self.stats = Collector::default();
+        }
+    }
+}
+
+#[cfg(not(feature = "stats"))]
+mod collector {
+    #[derive(Debug, Clone, Default)]
+    pub(crate) struct Collector;
+
+    impl Collector {
+        #[inline(always)]
+        pub(crate) fn link(&mut self, _parent_degree: usize) {}
+
+        #[inline(always)]
+        pub(crate) fn cut(&mut self) {}
+
+        #[inline(always)]
+        pub(crate) fn cascading_cut(&mut self) {}
+
+        #[inline(always)]
+        pub(crate) fn consolidation(&mut self, _roots: usize) {}
+
+        #[inline(always)]
+        pub(crate) fn comparison(&self) {}
+
+        #[inline(always)]
+        pub(crate) fn absorb(&mut self, _other: &Collector) {}
+    }
+}
+
+#[cfg(all(test, feature = "stats"))]
+mod tests {
+    use crate::testing::shaped_heap;
+    use crate::{FibonacciHeap, HeapStats};
+
+    #[test]
+    fn test_counts_cuts_and_links() {
+        let mut heap: FibonacciHeap<i32> = (0..32).collect();
+        let built = heap.stats();
+        assert_eq!(built.root_list_len, 32);
+        assert_eq!(built.comparisons, 31);
+
+        heap.extract_min();
+        let consolidated = heap.stats();
+        assert_eq!(consolidated.consolidations, 1);
+        assert_eq!(consolidated.max_root_list_len, 31);
+        assert_eq!(consolidated.links, 31 - consolidated.root_list_len as u64);
+        assert_eq!(consolidated.max_degree, 4);
+        assert_eq!(consolidated.cuts, 0);
+
+        // Cutting two children of an unmarked non-root parent cascades into
+        // it, and stops at its unmarked parent
+        let (mut heap, handles) = shaped_heap();
+        let before = heap.stats();
+        let parent = handles
+            .iter()
+            .filter_map(|handle| heap.resolve(handle).ok())
+            .find(|&at| {
+                let node = heap.node(at);
+                node.degree >= 2
+                    && !node.marked
+                    && heap.parent(at).is_some_and(|up| !heap.node(up).marked)
+            })
+            .unwrap();
+        let children = heap.ring_members(heap.first_child(parent).unwrap());
+        for &child in &children[..2] {
+            let handle = heap.handle(child);
+            heap.decrease_key(&handle, -5).unwrap();
+        }
+        let stats = heap.stats();
+        assert_eq!(stats.cascading_cuts, before.cascading_cuts + 1);
+        assert_eq!(stats.cuts, before.cuts + 3);
+        assert!(stats.comparisons > before.comparisons);
+    }
+
+    #[test]
+    fn test_reset_and_merge() {
+        let mut first: FibonacciHeap<_> = (0..10).collect();
+        let mut second: FibonacciHeap<_> = (0..10).collect();
+        first.extract_min();
+        second.extract_min();
+        let links = first.stats().links;
+
+        first.merge(second);
+        let merged = first.stats();
+        assert_eq!(merged.links, links * 2);
+        assert_eq!(merged.consolidations, 2);
+
+        first.reset_stats();
+        assert_eq!(
+            first.stats(),
+            HeapStats {
+                root_list_len: merged.root_list_len,
+                ..HeapStats::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,33 @@
+//! Fixtures shared by the unit tests of several modules.
+
+use crate::{FibonacciHeap, NodeHandle};
+
+/// A linear congruential generator, so random tests are reproducible
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Lcg(seed)
+    }
+
+    /// Returns a number in `0..bound`
+    pub fn next(&mut self, bound: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (self.0 >> 33) % bound
+    }
+}
+
+/// Builds a heap of 64 slots with several trees, some of them with marked nodes
+///
+/// Keys 0 and -2 have been extracted, and `handles[40]` holds the minimum, -1.
+pub fn shaped_heap() -> (FibonacciHeap<i32>, Vec<NodeHandle>) {
+    let (mut heap, handles) = FibonacciHeap::from_iter_with_handles(0..64);
+    heap.extract_min();
+    heap.decrease_key(&handles[40], -1).unwrap();
+    heap.decrease_key(&handles[41], -2).unwrap();
+    heap.extract_min();
+    (heap, handles)
+}
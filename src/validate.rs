@@ -0,0 +1,423 @@
+//! Structural self-checks for [`FibonacciHeap`].
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{Compare, FibonacciHeap, Node, NodeHandle, NodeRef};
+
+/// A broken heap invariant, as reported by [`FibonacciHeap::validate`]
+///
+/// Every variant names the node at which the problem was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// A child comes before its parent in the heap order
+    HeapOrder {
+        parent: NodeHandle,
+        child: NodeHandle,
+    },
+    /// A sibling link is not mirrored by its neighbour, points to a vacant
+    /// slot, or leads back into an already visited node
+    BrokenLink { node: NodeHandle },
+    /// A node's parent link does not point at the node whose child list holds it
+    ParentMismatch {
+        node: NodeHandle,
+        expected: Option<NodeHandle>,
+    },
+    /// A node's degree differs from the length of its child list
+    DegreeMismatch {
+        node: NodeHandle,
+        degree: usize,
+        children: usize,
+    },
+    /// The recorded length differs from the number of nodes in the trees
+    NodeCount { recorded: usize, reachable: usize },
+    /// A node is stored in the heap but not part of any tree
+    Unreachable { node: NodeHandle },
+    /// The minimum is not a root
+    MinNotRoot { min: NodeHandle },
+    /// A root comes before the recorded minimum
+    MinNotMinimal {
+        min: NodeHandle,
+        smaller: NodeHandle,
+    },
+    /// A root is marked; marks may only record losses below a parent
+    MarkedRoot { node: NodeHandle },
+    /// A tree is smaller than its root's degree allows, breaking the
+    /// logarithmic bound on degrees
+    DegreeBound {
+        node: NodeHandle,
+        degree: usize,
+        size: usize,
+    },
+}
+
+impl<T, C: Compare<T>> FibonacciHeap<T, C> {
+    /// Checks the structure of the heap and reports the first broken invariant
+    ///
+    /// This walks every node, so it takes O(n) time. It is meant for tests
+    /// and debugging; a heap used only through its public API always passes.
+    ///
+    /// The checks cover heap order, sibling and parent links, degrees, the
+    /// element count, the minimum, marks, and the Fibonacci bound that every
+    /// tree whose root has degree `k` holds at least `F(k + 2)` nodes.
+    ///
+    /// # Examples
+    /// ```
+    /// use fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap: FibonacciHeap<_> = (0..100).collect();
+    /// heap.extract_min();
+    /// assert_eq!(heap.validate(), Ok(()));
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantViolation> {
+        let mut visited = HashSet::with_capacity(self.node_count);
+        let mut order = Vec::with_capacity(self.node_count);
+
+        if let Some(min) = self.min {
+            if self.try_node(min).is_none() {
+                return Err(InvariantViolation::NodeCount {
+                    recorded: self.node_count,
+                    reachable: 0,
+                });
+            }
+            if self.node(min).parent.is_some() {
+                return Err(InvariantViolation::MinNotRoot {
+                    min: self.handle(min),
+                });
+            }
+
+            let mut pending = Vec::new();
+            for root in self.checked_ring(min, None, &mut visited)? {
+                if self.node(root).marked {
+                    return Err(InvariantViolation::MarkedRoot {
+                        node: self.handle(root),
+                    });
+                }
+                if self.precedes(root, min) {
+                    return Err(InvariantViolation::MinNotMinimal {
+                        min: self.handle(min),
+                        smaller: self.handle(root),
+                    });
+                }
+                pending.push(root);
+            }
+
+            while let Some(node) = pending.pop() {
+                order.push(node);
+                let children = match self.node(node).child {
+                    Some(child) => {
+                        self.checked_ring(child.target(node), Some(node), &mut visited)?
+                    }
+                    None => Vec::new(),
+                };
+                let degree = self.node(node).degree;
+                if children.len() != degree {
+                    return Err(InvariantViolation::DegreeMismatch {
+                        node: self.handle(node),
+                        degree,
+                        children: children.len(),
+                    });
+                }
+                for child in children {
+                    if self.precedes(child, node) {
+                        return Err(InvariantViolation::HeapOrder {
+                            parent: self.handle(node),
+                            child: self.handle(child),
+                        });
+                    }
+                    pending.push(child);
+                }
+            }
+        }
+
+        if order.len() != self.node_count {
+            return Err(InvariantViolation::NodeCount {
+                recorded: self.node_count,
+                reachable: order.len(),
+            });
+        }
+        for (segment, storage) in self.segments.iter().enumerate() {
+            for (slot, entry) in storage.slots.iter().enumerate() {
+                let at = NodeRef { segment, slot };
+                if entry.node.is_some() && !visited.contains(&at) {
+                    return Err(InvariantViolation::Unreachable {
+                        node: self.handle(at),
+                    });
+                }
+            }
+        }
+
+        // Descendants come after their ancestors in `order`, so walking it
+        // backwards adds every subtree to its parent once it is complete
+        let mut sizes: HashMap<NodeRef, usize> = order.iter().map(|&node| (node, 1)).collect();
+        for &node in order.iter().rev() {
+            let size = sizes[&node];
+            let degree = self.node(node).degree;
+            if size < fibonacci(degree + 2) {
+                return Err(InvariantViolation::DegreeBound {
+                    node: self.handle(node),
+                    degree,
+                    size,
+                });
+            }
+            if let Some(parent) = self.parent(node) {
+                *sizes.get_mut(&parent).expect(crate::LIVE_NODE) += size;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Walks the circular list through `start`, checking links in both
+    /// directions and that every member hangs below `parent`
+    fn checked_ring(
+        &self,
+        start: NodeRef,
+        parent: Option<NodeRef>,
+        visited: &mut HashSet<NodeRef>,
+    ) -> Result<Vec<NodeRef>, InvariantViolation> {
+        let mut members = Vec::new();
+        let mut current = start;
+        loop {
+            let node = self.try_node(current);
+            if node.is_none() || !visited.insert(current) {
+                // Reported against the node whose link led here
+                let from = members.last().copied().or(parent).unwrap_or(current);
+                return Err(InvariantViolation::BrokenLink {
+                    node: self.handle(from),
+                });
+            }
+            let node = node.expect(crate::LIVE_NODE);
+
+            if node.parent.map(|link| link.target(current)) != parent {
+                return Err(InvariantViolation::ParentMismatch {
+                    node: self.handle(current),
+                    expected: parent.map(|parent| self.handle(parent)),
+                });
+            }
+            let right = node.right.target(current);
+            let mirrored = self
+                .try_node(right)
+                .is_some_and(|neighbour| neighbour.left.target(right) == current);
+            if !mirrored {
+                return Err(InvariantViolation::BrokenLink {
+                    node: self.handle(current),
+                });
+            }
+
+            members.push(current);
+            current = right;
+            if current == start {
+                return Ok(members);
+            }
+        }
+    }
+
+    /// Looks up a node without assuming the position is valid
+    fn try_node(&self, at: NodeRef) -> Option<&Node<T>> {
+        self.segments
+            .get(at.segment)?
+            .slots
+            .get(at.slot)?
+            .node
+            .as_ref()
+    }
+}
+
+/// The `n`th Fibonacci number, saturating instead of overflowing
+fn fibonacci(n: usize) -> usize {
+    let (mut a, mut b) = (0usize, 1usize);
+    for _ in 0..n {
+        (a, b) = (b, a.saturating_add(b));
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::shaped_heap;
+
+    /// Finds a node that has a parent
+    fn some_child(heap: &FibonacciHeap<i32>) -> NodeRef {
+        (0..64)
+            .map(|slot| NodeRef { segment: 0, slot })
+            .find(|&at| heap.try_node(at).is_some_and(|node| node.parent.is_some()))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_valid_heaps_pass() {
+        assert_eq!(FibonacciHeap::<i32>::new().validate(), Ok(()));
+        let (mut heap, handles) = shaped_heap();
+        assert_eq!(heap.validate(), Ok(()));
+
+        let mut other = FibonacciHeap::new();
+        other.insert(-10).unwrap();
+        heap.merge(other);
+        heap.increase_key(&handles[50], 500).unwrap();
+        heap.delete(&handles[60]).unwrap();
+        assert_eq!(heap.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_cascading_cuts_keep_invariants() {
+        // A three-node tree whose child and grandchild are both cut
+        let mut heap = FibonacciHeap::new();
+        let deep = heap.insert(50).unwrap();
+        let middle = heap.insert(30).unwrap();
+        heap.insert(10).unwrap();
+        heap.extract_min();
+        heap.decrease_key(&middle, 5).unwrap();
+        heap.decrease_key(&deep, 1).unwrap();
+        assert_eq!(heap.validate(), Ok(()));
+
+        // Many cuts out of deep trees, marking and cutting their parents
+        let (mut heap, handles) = FibonacciHeap::<i64>::from_iter_with_handles(0..2000);
+        heap.extract_min();
+        for (i, handle) in handles.iter().enumerate().skip(1).step_by(3) {
+            heap.decrease_key(handle, -(i as i64)).unwrap();
+            if i % 100 == 1 {
+                assert_eq!(heap.validate(), Ok(()));
+            }
+        }
+        while heap.extract_min().is_some() {
+            if heap.len() % 100 == 0 {
+                assert_eq!(heap.validate(), Ok(()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mixed_operations_keep_invariants() {
+        let mut heap = FibonacciHeap::new();
+        let mut handles = Vec::new();
+        for i in 0..3000u64 {
+            // Multiplicative hashing scatters keys and operations deterministically
+            let spread = i.wrapping_mul(2_654_435_761) % 1000;
+            match spread % 8 {
+                0..=3 => handles.push(heap.insert(spread as i64).unwrap()),
+                4 => {
+                    heap.extract_min();
+                }
+                5 => {
+                    let handle = handles[(spread as usize) % handles.len()];
+                    let _ = heap.decrease_key(&handle, -(spread as i64));
+                }
+                6 => {
+                    let handle = handles[(spread as usize) % handles.len()];
+                    let _ = heap.update_key(&handle, spread as i64 * 2);
+                }
+                _ => {
+                    let handle = handles[(spread as usize) % handles.len()];
+                    let _ = heap.delete(&handle);
+                }
+            }
+            if i % 50 == 0 {
+                assert_eq!(heap.validate(), Ok(()));
+            }
+        }
+        assert_eq!(heap.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_reports_order_and_degree_violations() {
+        let (mut heap, _) = shaped_heap();
+        let child = some_child(&heap);
+        let parent = heap.parent(child).unwrap();
+        heap.node_mut(child).key = -100;
+        assert_eq!(
+            heap.validate(),
+            Err(InvariantViolation::HeapOrder {
+                parent: heap.handle(parent),
+                child: heap.handle(child),
+            })
+        );
+
+        let (mut heap, _) = shaped_heap();
+        let child = some_child(&heap);
+        let parent = heap.parent(child).unwrap();
+        heap.node_mut(parent).degree += 1;
+        assert!(matches!(
+            heap.validate(),
+            Err(InvariantViolation::DegreeMismatch { node, .. }) if node == heap.handle(parent)
+        ));
+    }
+
+    #[test]
+    fn test_reports_link_and_count_violations() {
+        let (mut heap, _) = shaped_heap();
+        let child = some_child(&heap);
+        heap.node_mut(child).parent = None;
+        assert!(matches!(
+            heap.validate(),
+            Err(InvariantViolation::ParentMismatch { node, expected: Some(_) }) if node == heap.handle(child)
+        ));
+
+        let (mut heap, _) = shaped_heap();
+        let min = heap.min.unwrap();
+        heap.set_left(min, min);
+        assert!(matches!(
+            heap.validate(),
+            Err(InvariantViolation::BrokenLink { .. })
+        ));
+
+        let (mut heap, _) = shaped_heap();
+        heap.node_count += 1;
+        assert_eq!(
+            heap.validate(),
+            Err(InvariantViolation::NodeCount {
+                recorded: 63,
+                reachable: 62,
+            })
+        );
+    }
+
+    #[test]
+    fn test_reports_min_and_mark_violations() {
+        let (mut heap, _) = shaped_heap();
+        let min = heap.min.unwrap();
+        let other = heap.right(min);
+        heap.min = Some(other);
+        assert!(matches!(
+            heap.validate(),
+            Err(InvariantViolation::MinNotMinimal { min, .. }) if min == heap.handle(other)
+        ));
+
+        let (mut heap, _) = shaped_heap();
+        let root = heap.min.unwrap();
+        heap.node_mut(root).marked = true;
+        assert_eq!(
+            heap.validate(),
+            Err(InvariantViolation::MarkedRoot {
+                node: heap.handle(root)
+            })
+        );
+    }
+
+    #[test]
+    fn test_reports_degree_bound_violation() {
+        // A root of degree 3 needs at least five nodes in its tree
+        let mut heap = FibonacciHeap::new();
+        let handles = heap.extend_with_handles([0, 1, 2, 3]);
+        let nodes: Vec<_> = handles.iter().map(|h| heap.resolve(h).unwrap()).collect();
+        for &child in &nodes[1..] {
+            heap.ring_remove(child);
+            heap.link(child, nodes[0]);
+        }
+        assert_eq!(
+            heap.validate(),
+            Err(InvariantViolation::DegreeBound {
+                node: handles[0],
+                degree: 3,
+                size: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn test_fibonacci_numbers() {
+        let first: Vec<_> = (0..10).map(fibonacci).collect();
+        assert_eq!(first, vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+        assert_eq!(fibonacci(200), usize::MAX);
+    }
+}